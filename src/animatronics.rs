@@ -0,0 +1,207 @@
+use rand::Rng;
+use serde::Deserialize;
+
+use std::cmp;
+
+use crate::behavior::{AiMode, Behavior, Move, WorldView};
+use crate::layout::{Door, Layout};
+use crate::map::Locations;
+use crate::roster::{Cove, Profile};
+
+// the highest AI level anyone can be set to
+pub const MAX_DIFFICULTY: u8 = 20;
+
+#[derive(PartialEq, Hash, Clone, Copy, Eq, Debug, Deserialize)]
+pub enum Tells {
+    Laughing,
+    Noise,
+    Footsteps,
+    Static,
+    Visual,
+    Breathing,
+    Hallucination,
+}
+
+impl Tells {
+    pub fn value(&self) -> &str {
+        match self {
+            Tells::Laughing => "l",
+            Tells::Noise => "n",
+            Tells::Footsteps => "f",
+            Tells::Static => "s",
+            Tells::Visual => "v",
+            Tells::Breathing => "b",
+            Tells::Hallucination => "g",
+        }
+    }
+
+    // what an audio only camera still picks up
+    pub fn is_audio(&self) -> bool {
+        matches!(
+            self,
+            Tells::Laughing | Tells::Noise | Tells::Footsteps | Tells::Breathing
+        )
+    }
+}
+
+pub struct Animatronic {
+    pub name: String,
+    pub location: Locations,
+    pub difficulty: u8,
+    pub current_tell: Tells,
+    pub profile: Profile,
+    // only used by cove characters
+    pub stage: u8,
+    pub sprinting: bool,
+    // in-game minutes since the last movement opportunity
+    pub waited: u32,
+    // picks the rooms, the cove rules above it stay in charge of Foxy
+    pub behavior: Box<dyn Behavior>,
+}
+
+impl Animatronic {
+    pub fn new(name: String, location: Locations, difficulty: u8) -> Animatronic {
+        let profile = Profile::new(name, location, difficulty);
+        Animatronic::from_profile(profile, difficulty)
+    }
+
+    pub fn from_profile(profile: Profile, difficulty: u8) -> Animatronic {
+        let clamped = cmp::min::<u8>(difficulty, MAX_DIFFICULTY);
+
+        Animatronic {
+            name: profile.name.clone(),
+            location: profile.start,
+            difficulty: clamped,
+            current_tell: Tells::Visual,
+            stage: 0,
+            sprinting: false,
+            waited: 0,
+            behavior: profile.ai.unwrap_or(AiMode::Classic).behavior(),
+            profile,
+        }
+    }
+
+    // the map wide AI mode, characters that pick their own in the roster
+    // keep it
+    pub fn set_ai(&mut self, ai: AiMode) {
+        if self.profile.ai.is_none() {
+            self.behavior = ai.behavior();
+        }
+    }
+
+    // lets `minutes` of in-game time pass, returns how many chances to move
+    // came up in them, one every `move_every` minutes of the profile
+    pub fn opportunities(&mut self, minutes: u32) -> u32 {
+        self.waited += minutes;
+        let count = self.waited / self.profile.move_every;
+        self.waited %= self.profile.move_every;
+        count
+    }
+
+    // returns the door that got banged on, if any
+    pub fn move_tick(
+        &mut self,
+        layout: &Layout,
+        closed_doors: &[Door],
+        camera_used: bool,
+        rng: &mut impl Rng,
+    ) -> Option<Door> {
+        if let Some(cove) = self.profile.cove.clone() {
+            return self.cove_tick(&cove, layout, closed_doors, camera_used, rng);
+        }
+
+        for door in closed_doors.iter() {
+            if self.location == door.room {
+                self.location = door.hallway;
+            }
+        }
+
+        // move the animatronic, a level 0 never passes the roll and a
+        // level 20 always does
+        let random_index = rng.random_range(1..=20);
+
+        if random_index <= self.difficulty {
+            {
+                // move the animatronic
+                let view = WorldView {
+                    layout,
+                    profile: &self.profile,
+                    location: self.location,
+                    difficulty: self.difficulty,
+                    closed_doors,
+                    camera_used,
+                };
+
+                let next = match self.behavior.choose_move(&view, rng) {
+                    Move::To(next) => next,
+                    Move::Stay => return None,
+                };
+
+                // Check to see if the animatronic is trying to move to the security office
+                // if it is, check if the door is closed
+                // if it is, don't move there
+                if closed_doors.iter().any(|door| door.room == next) {
+                    return None;
+                }
+
+                self.location = next;
+            }
+
+            {
+                // set the tell
+                self.current_tell = self.profile.pick_tell(self.location, rng);
+
+                if layout.is_door_room(self.location) {
+                    self.current_tell = Tells::Breathing;
+                }
+            }
+        }
+
+        None
+    }
+
+    fn cove_tick(
+        &mut self,
+        cove: &Cove,
+        layout: &Layout,
+        closed_doors: &[Door],
+        camera_used: bool,
+        rng: &mut impl Rng,
+    ) -> Option<Door> {
+        let door = *layout.door(cove.door)?;
+
+        if self.sprinting {
+            self.sprinting = false;
+
+            if closed_doors.iter().any(|closed| closed.side == door.side) {
+                self.location = self.profile.start;
+                self.stage = 0;
+                self.current_tell = Tells::Visual;
+                return Some(door);
+            }
+
+            self.location = layout.attack_room;
+            return None;
+        }
+
+        // keeping an eye on him slows him down, with the camera up this
+        // turn he needs to pass a second roll as well
+        let random_index = rng.random_range(1..=20);
+        let mut advances = random_index <= self.difficulty;
+        if advances && camera_used {
+            advances = rng.random_range(1..=20) <= self.difficulty;
+        }
+
+        if advances {
+            self.stage += 1;
+
+            if self.stage >= cove.stages {
+                self.location = door.hallway;
+                self.sprinting = true;
+                self.current_tell = Tells::Footsteps;
+            }
+        }
+
+        None
+    }
+}
@@ -0,0 +1,176 @@
+use std::fmt;
+
+use rand::{Rng, RngCore};
+use serde::Deserialize;
+
+use crate::layout::{Door, Layout};
+use crate::map::Locations;
+use crate::roster::Profile;
+
+// what an animatronic gets to know when it is its turn to move
+pub struct WorldView<'a> {
+    pub layout: &'a Layout,
+    pub profile: &'a Profile,
+    pub location: Locations,
+    pub difficulty: u8,
+    pub closed_doors: &'a [Door],
+    pub camera_used: bool,
+}
+
+impl WorldView<'_> {
+    // the rooms this character may step into from where it stands
+    pub fn exits(&self) -> Vec<Locations> {
+        self.profile.exits(self.layout, self.location)
+    }
+
+    pub fn distance(&self, from: Locations, to: Locations) -> u8 {
+        self.profile
+            .distance(self.layout, from, to)
+            .unwrap_or(u8::MAX)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Move {
+    Stay,
+    To(Locations),
+}
+
+// picks the next room once an animatronic passed its AI roll, the closed
+// doors are enforced by the caller no matter what gets picked
+pub trait Behavior {
+    fn choose_move(&mut self, ctx: &WorldView, rng: &mut dyn RngCore) -> Move;
+}
+
+// any exit, all equally likely
+pub struct RandomWalk;
+
+impl Behavior for RandomWalk {
+    fn choose_move(&mut self, ctx: &WorldView, rng: &mut dyn RngCore) -> Move {
+        let exits = ctx.exits();
+        if exits.is_empty() {
+            return Move::Stay;
+        }
+
+        Move::To(exits[rng.random_range(0..exits.len())])
+    }
+}
+
+// heads for its target, the higher the AI level the more often it takes
+// the shortest way instead of a random one
+pub struct GoalSeeking;
+
+impl GoalSeeking {
+    fn step_toward(ctx: &WorldView, target: Locations, rng: &mut dyn RngCore) -> Move {
+        let exits = ctx.exits();
+        if exits.is_empty() {
+            return Move::Stay;
+        }
+
+        if rng.random_range(0..20) >= ctx.difficulty {
+            return Move::To(exits[rng.random_range(0..exits.len())]);
+        }
+
+        let closest = exits
+            .iter()
+            .min_by_key(|exit| ctx.distance(**exit, target))
+            .copied()
+            .unwrap_or(ctx.location);
+        Move::To(closest)
+    }
+}
+
+impl Behavior for GoalSeeking {
+    fn choose_move(&mut self, ctx: &WorldView, rng: &mut dyn RngCore) -> Move {
+        GoalSeeking::step_toward(ctx, ctx.profile.target(ctx.layout), rng)
+    }
+}
+
+// walks to its door and waits in the hallway for it to open, instead of
+// wandering off when it finds it closed
+pub struct DoorCamper;
+
+impl Behavior for DoorCamper {
+    fn choose_move(&mut self, ctx: &WorldView, rng: &mut dyn RngCore) -> Move {
+        let door = match ctx.profile.door.and_then(|side| ctx.layout.door(side)) {
+            Some(door) => *door,
+            None => match ctx
+                .layout
+                .doors
+                .iter()
+                .min_by_key(|door| ctx.distance(ctx.location, door.hallway))
+            {
+                Some(door) => *door,
+                None => return GoalSeeking.choose_move(ctx, rng),
+            },
+        };
+
+        if ctx.location == door.hallway {
+            if ctx
+                .closed_doors
+                .iter()
+                .any(|closed| closed.side == door.side)
+            {
+                return Move::Stay;
+            }
+            return Move::To(door.room);
+        }
+
+        GoalSeeking::step_toward(ctx, door.hallway, rng)
+    }
+}
+
+// freezes while the camera is up, hunts like GoalSeeking otherwise
+pub struct CameraShy;
+
+impl Behavior for CameraShy {
+    fn choose_move(&mut self, ctx: &WorldView, rng: &mut dyn RngCore) -> Move {
+        if ctx.camera_used {
+            return Move::Stay;
+        }
+
+        GoalSeeking.choose_move(ctx, rng)
+    }
+}
+
+// the built in behaviors by name, for the roster file and --ai
+#[derive(PartialEq, Clone, Copy, Eq, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AiMode {
+    Classic,
+    Hunt,
+    Camper,
+    CameraShy,
+}
+
+impl AiMode {
+    pub fn parse(text: &str) -> Option<AiMode> {
+        match text.trim().to_lowercase().as_str() {
+            "classic" => Some(AiMode::Classic),
+            "hunt" => Some(AiMode::Hunt),
+            "camper" => Some(AiMode::Camper),
+            "camera-shy" => Some(AiMode::CameraShy),
+            _ => None,
+        }
+    }
+
+    pub fn behavior(&self) -> Box<dyn Behavior> {
+        match self {
+            AiMode::Classic => Box::new(RandomWalk),
+            AiMode::Hunt => Box::new(GoalSeeking),
+            AiMode::Camper => Box::new(DoorCamper),
+            AiMode::CameraShy => Box::new(CameraShy),
+        }
+    }
+}
+
+impl fmt::Display for AiMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AiMode::Classic => write!(f, "classic"),
+            AiMode::Hunt => write!(f, "hunt"),
+            AiMode::Camper => write!(f, "camper"),
+            AiMode::CameraShy => write!(f, "camera-shy"),
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use rand::Rng;
+
+// one tick in this many the eyes show up once the lights are out
+pub const EYES_ODDS: u32 = 2;
+// how many ticks the music box and the darkness after it can last
+pub const MAX_JINGLE: u8 = 4;
+pub const MAX_DARKNESS: u8 = 2;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PowerOut {
+    // the power is still on
+    Powered,
+    // everything just went off, nobody is there yet
+    LightsOut,
+    // glowing eyes in the left doorway
+    Eyes,
+    // the music box plays for this many more ticks
+    Jingle(u8),
+    // silence for this many more ticks, then the attack
+    Darkness(u8),
+    Attack,
+}
+
+// what happens after the power runs out, every step of it is rolled with
+// the night rng so replays see the same blackout
+#[derive(Clone, Copy, Debug)]
+pub struct Blackout {
+    pub state: PowerOut,
+}
+
+impl Default for Blackout {
+    fn default() -> Blackout {
+        Blackout::new()
+    }
+}
+
+impl Blackout {
+    pub fn new() -> Blackout {
+        Blackout {
+            state: PowerOut::Powered,
+        }
+    }
+
+    pub fn start(&mut self) {
+        if self.state == PowerOut::Powered {
+            self.state = PowerOut::LightsOut;
+        }
+    }
+
+    // moves the sequence on by one tick, returns a message for the player
+    // and whether the attack came
+    pub fn tick(&mut self, rng: &mut impl Rng) -> (Option<&'static str>, bool) {
+        match self.state {
+            PowerOut::Powered | PowerOut::Attack => (None, false),
+            PowerOut::LightsOut => {
+                if rng.random_range(0..EYES_ODDS) != 0 {
+                    return (None, false);
+                }
+                self.state = PowerOut::Eyes;
+                (Some("Two glowing eyes appear in the left doorway."), false)
+            }
+            PowerOut::Eyes => {
+                self.state = PowerOut::Jingle(rng.random_range(1..=MAX_JINGLE));
+                (
+                    Some("A music box starts playing the Toreador March."),
+                    false,
+                )
+            }
+            PowerOut::Jingle(1) => {
+                self.state = PowerOut::Darkness(rng.random_range(1..=MAX_DARKNESS));
+                (
+                    Some("The music stops. The eyes are gone and everything goes dark."),
+                    false,
+                )
+            }
+            PowerOut::Jingle(ticks) => {
+                self.state = PowerOut::Jingle(ticks - 1);
+                (Some("The music box keeps playing."), false)
+            }
+            PowerOut::Darkness(1) | PowerOut::Darkness(0) => {
+                self.state = PowerOut::Attack;
+                (Some("Something lunges out of the dark!"), true)
+            }
+            PowerOut::Darkness(ticks) => {
+                self.state = PowerOut::Darkness(ticks - 1);
+                (None, false)
+            }
+        }
+    }
+}
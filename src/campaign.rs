@@ -0,0 +1,176 @@
+use std::fmt;
+
+use serde::Deserialize;
+
+use crate::animatronics::Animatronic;
+use crate::clock::END_TIME;
+use crate::extras::{animatronics_at, validate_levels, Levels};
+use crate::roster::{quoted, unquoted, Roster};
+
+const DEFAULT_CAMPAIGN: &str = include_str!("../characters/campaign.toml");
+
+// one AI level bump during the night: on the hour `hour` (2 is 2 AM)
+// `name` gains `gain` levels
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RampStep {
+    pub hour: u32,
+    pub name: String,
+    pub gain: u8,
+}
+
+impl RampStep {
+    // the replay form, like 2:Bonnie+1 or 3:"Toy Bonnie"+2
+    pub fn parse(text: &str) -> Option<RampStep> {
+        let (hour, rest) = text.split_once(':')?;
+        let (name, gain) = rest.rsplit_once('+')?;
+
+        Some(RampStep {
+            hour: hour.parse::<u32>().ok()?,
+            name: unquoted(name).to_string(),
+            gain: gain.parse::<u8>().ok()?,
+        })
+    }
+}
+
+impl fmt::Display for RampStep {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}+{}", self.hour, quoted(&self.name), self.gain)
+    }
+}
+
+// the levels gained at one hour of the night, as the campaign file has it
+#[derive(Clone, Debug, Deserialize)]
+pub struct HourRamp {
+    pub hour: u32,
+    pub levels: Levels,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct CampaignNight {
+    pub levels: Levels,
+    // replaces the campaign wide ramp for this night
+    #[serde(default)]
+    pub ramp: Option<Vec<HourRamp>>,
+}
+
+// the AI levels of every night of the campaign, in order, and how they go
+// up as the night goes on
+#[derive(Clone, Debug, Deserialize)]
+pub struct Campaign {
+    #[serde(default)]
+    pub ramp: Vec<HourRamp>,
+    pub nights: Vec<CampaignNight>,
+}
+
+// the hours in a ramp as single steps, earliest first
+fn ramp_steps(ramp: &[HourRamp]) -> Vec<RampStep> {
+    let mut steps: Vec<RampStep> = ramp
+        .iter()
+        .flat_map(|hour| {
+            hour.levels.iter().map(|(name, gain)| RampStep {
+                hour: hour.hour,
+                name: name.clone(),
+                gain: *gain,
+            })
+        })
+        .collect();
+    steps.sort_by(|a, b| a.hour.cmp(&b.hour).then_with(|| a.name.cmp(&b.name)));
+    steps
+}
+
+fn validate_ramp(what: &str, ramp: &[HourRamp], roster: &Roster) -> Result<(), String> {
+    let last_hour = END_TIME / 60 - 1;
+    for hour in ramp.iter() {
+        if hour.hour == 0 || hour.hour > last_hour {
+            return Err(format!(
+                "{what}: a ramp at {} never happens, hours go from 1 to {last_hour}",
+                hour.hour
+            ));
+        }
+        validate_levels(&format!("{what} at {}", hour.hour), &hour.levels, roster)?;
+    }
+
+    Ok(())
+}
+
+impl Campaign {
+    pub fn parse(text: &str, roster: &Roster) -> Result<Campaign, String> {
+        let campaign: Campaign = toml::from_str(text).map_err(|err| err.to_string())?;
+        campaign.validate(roster)?;
+        Ok(campaign)
+    }
+
+    pub fn load(path: &str, roster: &Roster) -> Result<Campaign, String> {
+        let text = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+        Campaign::parse(&text, roster)
+    }
+
+    // the built in campaign, trimmed down to whoever is in the roster so a
+    // custom roster can still play it
+    pub fn default_for(roster: &Roster) -> Campaign {
+        let mut campaign: Campaign =
+            toml::from_str(DEFAULT_CAMPAIGN).expect("the built in campaign is valid");
+        let known = |levels: &mut Levels| levels.retain(|name, _| roster.find(name).is_some());
+
+        for hour in campaign.ramp.iter_mut() {
+            known(&mut hour.levels);
+        }
+        for night in campaign.nights.iter_mut() {
+            known(&mut night.levels);
+            for hour in night.ramp.iter_mut().flatten() {
+                known(&mut hour.levels);
+            }
+        }
+        campaign
+    }
+
+    pub fn len(&self) -> u8 {
+        self.nights.len() as u8
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nights.is_empty()
+    }
+
+    // the cast of a night, counting from 1. past the end the last night
+    // is played again
+    pub fn animatronics(&self, night: u8, roster: &Roster) -> Vec<Animatronic> {
+        animatronics_at(&self.nights[self.index(night)].levels, roster)
+    }
+
+    fn index(&self, night: u8) -> usize {
+        (night.max(1) as usize - 1).min(self.nights.len() - 1)
+    }
+
+    // the campaign wide ramp, for Night 6
+    pub fn default_ramp(&self) -> Vec<RampStep> {
+        ramp_steps(&self.ramp)
+    }
+
+    pub fn ramp(&self, night: u8) -> Vec<RampStep> {
+        match &self.nights[self.index(night)].ramp {
+            Some(ramp) => ramp_steps(ramp),
+            None => self.default_ramp(),
+        }
+    }
+
+    pub fn validate(&self, roster: &Roster) -> Result<(), String> {
+        if self.nights.is_empty() {
+            return Err(String::from("the campaign has no nights"));
+        }
+        if self.nights.len() > u8::MAX as usize {
+            return Err(format!("the campaign has more than {} nights", u8::MAX));
+        }
+
+        validate_ramp("ramp", &self.ramp, roster)?;
+        for (number, night) in self.nights.iter().enumerate() {
+            let what = format!("night {}", number + 1);
+            validate_levels(&what, &night.levels, roster)?;
+            if let Some(ramp) = &night.ramp {
+                validate_ramp(&what, ramp, roster)?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+use std::fmt;
+use std::time::Duration;
+
+pub const START_TIME: u32 = 0;
+pub const END_TIME: u32 = 6 * 60;
+pub const TICK_RATE: u32 = 15; // 15 minutes at a time
+
+// how long one tick lasts on the wall clock in real-time mode, when an
+// in-game hour takes `hour_seconds`
+pub fn tick_duration(hour_seconds: f64) -> Duration {
+    Duration::from_secs_f64(hour_seconds * TICK_RATE as f64 / 60.0)
+}
+
+// in-game minutes since midnight
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Clock {
+    pub time: u32,
+}
+
+impl Clock {
+    pub fn new() -> Clock {
+        Clock { time: START_TIME }
+    }
+
+    pub fn advance(&mut self) {
+        self.time += TICK_RATE;
+    }
+
+    pub fn is_dawn(&self) -> bool {
+        self.time >= END_TIME
+    }
+
+    pub fn hours_minutes(&self) -> (u32, u32) {
+        display_time(self.time)
+    }
+}
+
+impl Default for Clock {
+    fn default() -> Clock {
+        Clock::new()
+    }
+}
+
+impl fmt::Display for Clock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (hours, minutes) = self.hours_minutes();
+        write!(f, "{:02}:{:02}", hours, minutes)
+    }
+}
+
+pub fn display_time(time: u32) -> (u32, u32) {
+    let hours = time / 60;
+    let minutes = time % 60;
+    (hours, minutes)
+}
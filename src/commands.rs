@@ -0,0 +1,439 @@
+use std::fmt;
+
+use serde::Deserialize;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Side {
+    Left,
+    Right,
+}
+
+// a camera on the map, a number with an optional letter like 2A or 7
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Deserialize)]
+#[serde(try_from = "String")]
+pub struct CameraId {
+    pub number: u8,
+    pub letter: Option<char>,
+}
+
+impl CameraId {
+    pub fn parse(text: &str) -> Option<CameraId> {
+        let text = text.trim();
+        let digits = text.chars().take_while(|c| c.is_ascii_digit()).count();
+        let number = text[..digits].parse::<u8>().ok()?;
+
+        let mut rest = text[digits..].chars();
+        let letter = match (rest.next(), rest.next()) {
+            (None, _) => None,
+            (Some(c), None) if c.is_ascii_alphabetic() => Some(c.to_ascii_uppercase()),
+            _ => return None,
+        };
+
+        Some(CameraId { number, letter })
+    }
+}
+
+impl TryFrom<String> for CameraId {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<CameraId, String> {
+        CameraId::parse(&text).ok_or_else(|| format!("'{text}' is not a camera, try 1A or 7"))
+    }
+}
+
+impl fmt::Display for CameraId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.number)?;
+        if let Some(letter) = self.letter {
+            write!(f, "{letter}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Command {
+    // toggles
+    Door(Side),
+    Light(Side),
+    // explicit, doing nothing if it already is that way
+    Close(Side),
+    Open(Side),
+    LightOn(Side),
+    LightOff(Side),
+    // the monitor on whatever camera was picked last
+    Camera,
+    View(CameraId),
+    Sit,
+}
+
+impl fmt::Display for Side {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Side::Left => write!(f, "left"),
+            Side::Right => write!(f, "right"),
+        }
+    }
+}
+
+// every word the parser knows, "did you mean" picks from these
+const WORDS: &[&str] = &[
+    "left", "l", "right", "r", "door", "doors", "d", "light", "lights", "lamp", "close", "shut",
+    "open", "on", "off", "toggle", "the", "turn", "switch", "check", "use", "camera", "cameras",
+    "cam", "cams", "c", "monitor", "sit", "wait", "s",
+];
+
+#[derive(Clone, Copy, PartialEq)]
+enum Device {
+    Door,
+    Light,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Switch {
+    Close,
+    Open,
+    On,
+    Off,
+    Toggle,
+}
+
+// why a single action didn't parse
+enum ParseError {
+    UnknownWord,
+    Invalid(String),
+}
+
+impl Command {
+    // one action, like "left door", "close left", "ld", "cam 2a" or
+    // "right light on"
+    pub fn parse(input: &str) -> Option<Command> {
+        parse_action(input).ok()
+    }
+
+    // a whole turn, actions are separated by ';'. nothing is returned
+    // unless every action makes sense, the error says what went wrong
+    pub fn parse_line(input: &str) -> Result<Vec<Command>, String> {
+        let mut commands = Vec::new();
+
+        for action in input.split(';') {
+            if action.trim().is_empty() {
+                continue;
+            }
+
+            match parse_action(action) {
+                Ok(command) => commands.push(command),
+                Err(ParseError::Invalid(reason)) => return Err(reason),
+                Err(ParseError::UnknownWord) => {
+                    let action = action.trim();
+                    return Err(match suggest(action) {
+                        Some(guess) => {
+                            format!("Unknown command '{action}', did you mean '{guess}'?")
+                        }
+                        None => format!("Unknown command '{action}'"),
+                    });
+                }
+            }
+        }
+
+        if commands.is_empty() {
+            return Err(String::from("Nothing to do, type a command or 'sit'"));
+        }
+
+        Ok(commands)
+    }
+}
+
+fn parse_action(input: &str) -> Result<Command, ParseError> {
+    let input = input.trim().to_lowercase();
+    let words: Vec<&str> = input.split_whitespace().collect();
+
+    // the short forms only work on their own
+    if let [word] = words.as_slice() {
+        match *word {
+            "ld" => return Ok(Command::Door(Side::Left)),
+            "rd" => return Ok(Command::Door(Side::Right)),
+            "ll" => return Ok(Command::Light(Side::Left)),
+            "rl" => return Ok(Command::Light(Side::Right)),
+            _ => {}
+        }
+    }
+
+    let mut side = None;
+    let mut device = None;
+    let mut switch = None;
+    let mut other = None;
+    let mut camera = None;
+
+    for word in words.iter() {
+        match *word {
+            _ if CameraId::parse(word).is_some() => camera = CameraId::parse(word),
+            "left" | "l" | "right" | "r" => {
+                let this = match *word {
+                    "left" | "l" => Side::Left,
+                    _ => Side::Right,
+                };
+                if side.is_some_and(|side| side != this) {
+                    return Err(ParseError::Invalid(String::from(
+                        "One side at a time, split them with ';'",
+                    )));
+                }
+                side = Some(this);
+            }
+            "door" | "doors" | "d" => device = Some(Device::Door),
+            "light" | "lights" | "lamp" => device = Some(Device::Light),
+            "close" | "shut" => switch = Some(Switch::Close),
+            "open" => switch = Some(Switch::Open),
+            "on" => switch = Some(Switch::On),
+            "off" => switch = Some(Switch::Off),
+            "toggle" => switch = Some(Switch::Toggle),
+            "the" | "turn" | "switch" | "check" | "use" => {}
+            "camera" | "cameras" | "cam" | "cams" | "c" | "monitor" => {
+                other = Some(Command::Camera)
+            }
+            "sit" | "wait" | "s" => other = Some(Command::Sit),
+            _ => return Err(ParseError::UnknownWord),
+        }
+    }
+
+    // "cam 2a" or just "2a"
+    if let Some(id) = camera {
+        if side.is_some() || device.is_some() || switch.is_some() || other == Some(Command::Sit) {
+            return Err(ParseError::Invalid(format!(
+                "'{input}' mixes up a camera with something else, split them with ';'"
+            )));
+        }
+        return Ok(Command::View(id));
+    }
+
+    if let Some(command) = other {
+        if side.is_some() || device.is_some() || switch.is_some() {
+            return Err(ParseError::Invalid(format!(
+                "'{input}' mixes up {command} with a door or light, split them with ';'"
+            )));
+        }
+        return Ok(command);
+    }
+
+    // "close left" has to be a door and "left on" a light
+    let device = match (device, switch) {
+        (Some(device), _) => device,
+        (None, Some(Switch::Close | Switch::Open)) => Device::Door,
+        (None, Some(Switch::On | Switch::Off)) => Device::Light,
+        (None, _) if side.is_some() => {
+            return Err(ParseError::Invalid(format!(
+                "'{input}' what? Say 'door' or 'light'"
+            )))
+        }
+        _ => return Err(ParseError::UnknownWord),
+    };
+
+    let Some(side) = side else {
+        return Err(ParseError::Invalid(format!(
+            "Which side? Say 'left' or 'right' in '{input}'"
+        )));
+    };
+
+    match (device, switch.unwrap_or(Switch::Toggle)) {
+        (Device::Door, Switch::Toggle) => Ok(Command::Door(side)),
+        (Device::Door, Switch::Close) => Ok(Command::Close(side)),
+        (Device::Door, Switch::Open) => Ok(Command::Open(side)),
+        (Device::Light, Switch::Toggle) => Ok(Command::Light(side)),
+        (Device::Light, Switch::On) => Ok(Command::LightOn(side)),
+        (Device::Light, Switch::Off) => Ok(Command::LightOff(side)),
+        (Device::Door, _) => Err(ParseError::Invalid(String::from(
+            "Doors open and close, they don't turn on or off",
+        ))),
+        (Device::Light, _) => Err(ParseError::Invalid(String::from(
+            "Lights turn on and off, they don't open or close",
+        ))),
+    }
+}
+
+// swaps every unknown word for the closest known one, if that gives a
+// command it is what the player probably meant
+fn suggest(action: &str) -> Option<Command> {
+    let lowered = action.to_lowercase();
+    let mut repaired = Vec::new();
+
+    for word in lowered.split_whitespace() {
+        if WORDS.contains(&word) || CameraId::parse(word).is_some() {
+            repaired.push(word);
+            continue;
+        }
+
+        let (closest, distance) = WORDS
+            .iter()
+            .map(|known| (*known, levenshtein(word, known)))
+            .min_by_key(|(_, distance)| *distance)?;
+
+        // one typo in a short word, two in a longer one
+        let allowed = if word.len() > 4 { 2 } else { 1 };
+        if distance > allowed {
+            return None;
+        }
+        repaired.push(closest);
+    }
+
+    parse_action(&repaired.join(" ")).ok()
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitute = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitute.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Command::Door(side) => write!(f, "{side} door"),
+            Command::Light(side) => write!(f, "{side} light"),
+            Command::Close(side) => write!(f, "close {side} door"),
+            Command::Open(side) => write!(f, "open {side} door"),
+            Command::LightOn(side) => write!(f, "{side} light on"),
+            Command::LightOff(side) => write!(f, "{side} light off"),
+            Command::Camera => write!(f, "camera"),
+            Command::View(id) => write!(f, "cam {id}"),
+            Command::Sit => write!(f, "sit"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cam(text: &str) -> Command {
+        Command::View(CameraId::parse(text).unwrap())
+    }
+
+    #[test]
+    fn case_does_not_matter() {
+        assert_eq!(Command::parse("LEFT DOOR"), Some(Command::Door(Side::Left)));
+        assert_eq!(
+            Command::parse("Right Light"),
+            Some(Command::Light(Side::Right))
+        );
+        assert_eq!(Command::parse("  SiT "), Some(Command::Sit));
+    }
+
+    #[test]
+    fn aliases_mean_the_same_thing() {
+        for text in ["ld", "l door", "left d", "left door", "door left"] {
+            assert_eq!(
+                Command::parse(text),
+                Some(Command::Door(Side::Left)),
+                "{text}"
+            );
+        }
+        for text in ["rl", "r light", "right lamp", "check the right light"] {
+            assert_eq!(
+                Command::parse(text),
+                Some(Command::Light(Side::Right)),
+                "{text}"
+            );
+        }
+        for text in ["camera", "cam", "c", "monitor", "use the cameras"] {
+            assert_eq!(Command::parse(text), Some(Command::Camera), "{text}");
+        }
+        for text in ["sit", "wait", "s"] {
+            assert_eq!(Command::parse(text), Some(Command::Sit), "{text}");
+        }
+    }
+
+    #[test]
+    fn explicit_states_never_toggle() {
+        assert_eq!(
+            Command::parse("close left"),
+            Some(Command::Close(Side::Left))
+        );
+        assert_eq!(
+            Command::parse("shut the right door"),
+            Some(Command::Close(Side::Right))
+        );
+        assert_eq!(
+            Command::parse("open right door"),
+            Some(Command::Open(Side::Right))
+        );
+        assert_eq!(
+            Command::parse("left light on"),
+            Some(Command::LightOn(Side::Left))
+        );
+        assert_eq!(
+            Command::parse("turn right off"),
+            Some(Command::LightOff(Side::Right))
+        );
+        assert_eq!(
+            Command::parse("toggle left door"),
+            Some(Command::Door(Side::Left))
+        );
+    }
+
+    #[test]
+    fn cameras_by_id() {
+        assert_eq!(Command::parse("cam 2a"), Some(cam("2A")));
+        assert_eq!(Command::parse("1C"), Some(cam("1C")));
+        assert_eq!(Command::parse("camera 7"), Some(cam("7")));
+        assert_eq!(cam("4b").to_string(), "cam 4B");
+    }
+
+    #[test]
+    fn a_turn_chains_actions_with_semicolons() {
+        assert_eq!(
+            Command::parse_line("close left; rl ;; cam 2B"),
+            Ok(vec![
+                Command::Close(Side::Left),
+                Command::Light(Side::Right),
+                cam("2B"),
+            ])
+        );
+        assert!(Command::parse_line(" ; ").is_err());
+    }
+
+    #[test]
+    fn one_bad_action_spoils_the_whole_turn() {
+        let err = Command::parse_line("close left; dance").unwrap_err();
+        assert_eq!(err, "Unknown command 'dance'");
+    }
+
+    #[test]
+    fn conflicting_words_are_errors() {
+        let err = |text: &str| Command::parse_line(text).unwrap_err();
+
+        assert!(err("left right door").contains("One side at a time"));
+        assert!(err("left door on").contains("they don't turn on or off"));
+        assert!(err("close left light").contains("they don't open or close"));
+        assert!(err("left").contains("Say 'door' or 'light'"));
+        assert!(err("close door").contains("Which side?"));
+        assert!(err("cam 2a left").contains("mixes up a camera"));
+        assert!(err("sit left door").contains("mixes up sit"));
+    }
+
+    #[test]
+    fn typos_get_a_suggestion() {
+        assert_eq!(
+            Command::parse_line("lft door").unwrap_err(),
+            "Unknown command 'lft door', did you mean 'left door'?"
+        );
+        assert_eq!(
+            Command::parse_line("clsoe rihgt").unwrap_err(),
+            "Unknown command 'clsoe rihgt', did you mean 'close right door'?"
+        );
+        assert_eq!(
+            Command::parse_line("xyzzy").unwrap_err(),
+            "Unknown command 'xyzzy'"
+        );
+    }
+}
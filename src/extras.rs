@@ -0,0 +1,104 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::Deserialize;
+
+use crate::animatronics::{Animatronic, MAX_DIFFICULTY};
+use crate::roster::Roster;
+
+const DEFAULT_EXTRAS: &str = include_str!("../characters/extras.toml");
+
+// AI levels by character name
+pub type Levels = HashMap<String, u8>;
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Preset {
+    pub name: String,
+    pub levels: Levels,
+}
+
+// everything unlocked after the campaign: Night 6 and the Custom Night
+// presets
+#[derive(Clone, Debug, Deserialize)]
+pub struct Extras {
+    pub night_6: Levels,
+    #[serde(default)]
+    pub presets: Vec<Preset>,
+}
+
+// the roster at the given levels, in roster order, anyone without a level
+// sits at 0
+pub fn animatronics_at(levels: &Levels, roster: &Roster) -> Vec<Animatronic> {
+    roster
+        .characters
+        .iter()
+        .map(|profile| {
+            let difficulty = levels
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(&profile.name))
+                .map_or(0, |(_, difficulty)| *difficulty);
+            Animatronic::from_profile(profile.clone(), difficulty)
+        })
+        .collect()
+}
+
+pub(crate) fn validate_levels(what: &str, levels: &Levels, roster: &Roster) -> Result<(), String> {
+    for (name, difficulty) in levels.iter() {
+        if roster.find(name).is_none() {
+            return Err(format!("{what}: {name} is not in the roster"));
+        }
+        if *difficulty > MAX_DIFFICULTY {
+            return Err(format!(
+                "{what}: {name} is at {difficulty}, levels go up to {MAX_DIFFICULTY}"
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+impl Extras {
+    pub fn parse(text: &str, roster: &Roster) -> Result<Extras, String> {
+        let extras: Extras = toml::from_str(text).map_err(|err| err.to_string())?;
+        extras.validate(roster)?;
+        Ok(extras)
+    }
+
+    pub fn load(path: &str, roster: &Roster) -> Result<Extras, String> {
+        let text = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+        Extras::parse(&text, roster)
+    }
+
+    // the built in nights, trimmed down to whoever is in the roster so a
+    // custom roster still gets them
+    pub fn default_for(roster: &Roster) -> Extras {
+        let mut extras: Extras =
+            toml::from_str(DEFAULT_EXTRAS).expect("the built in extras are valid");
+        let known = |levels: &mut Levels| levels.retain(|name, _| roster.find(name).is_some());
+
+        known(&mut extras.night_6);
+        for preset in extras.presets.iter_mut() {
+            known(&mut preset.levels);
+        }
+        extras
+    }
+
+    pub fn find(&self, name: &str) -> Option<&Preset> {
+        self.presets
+            .iter()
+            .find(|preset| preset.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn validate(&self, roster: &Roster) -> Result<(), String> {
+        validate_levels("night_6", &self.night_6, roster)?;
+
+        let mut names = HashSet::new();
+        for preset in self.presets.iter() {
+            if !names.insert(preset.name.to_lowercase()) {
+                return Err(format!("{} is a preset twice", preset.name));
+            }
+            validate_levels(&preset.name, &preset.levels, roster)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use rand::Rng;
+
+use crate::map::Locations;
+
+pub const NAME: &str = "Golden Freddy";
+// one night in this many has him hiding on the cameras
+pub const HALLUCINATION_ODDS: u32 = 20;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Hallucination {
+    // not tonight
+    Dormant,
+    // waiting for the camera to land on his room
+    Armed,
+    // seen on camera, shows up in the office on the next tick
+    Seen,
+    // sitting in the office, opening the camera again makes him go away
+    InOffice,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct GoldenFreddy {
+    pub room: Option<Locations>,
+    pub state: Hallucination,
+}
+
+impl GoldenFreddy {
+    pub fn new(room: Option<Locations>) -> GoldenFreddy {
+        GoldenFreddy {
+            room,
+            state: Hallucination::Dormant,
+        }
+    }
+
+    // decides at dusk if he is around tonight
+    pub fn roll(&mut self, rng: &mut impl Rng) {
+        self.state = Hallucination::Dormant;
+
+        if self.room.is_some() && rng.random_range(0..HALLUCINATION_ODDS) == 0 {
+            self.state = Hallucination::Armed;
+        }
+    }
+
+    // called with every room the camera shows, returns a message when
+    // something happened
+    pub fn camera_viewed(&mut self, viewed: &[Locations]) -> Option<&'static str> {
+        match self.state {
+            Hallucination::Armed if self.room.is_some_and(|room| viewed.contains(&room)) => {
+                self.state = Hallucination::Seen;
+                None
+            }
+            Hallucination::InOffice => {
+                self.state = Hallucination::Dormant;
+                Some("When you look up from the camera the office is empty again.")
+            }
+            _ => None,
+        }
+    }
+
+    // returns a message for the player and whether he got them
+    pub fn tick(&mut self) -> (Option<&'static str>, bool) {
+        match self.state {
+            Hallucination::Seen => {
+                self.state = Hallucination::InOffice;
+                (
+                    Some("You lower the camera. Someone is slumped in the corner of your office."),
+                    false,
+                )
+            }
+            Hallucination::InOffice => (Some("IT'S ME."), true),
+            _ => (None, false),
+        }
+    }
+}
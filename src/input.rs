@@ -0,0 +1,178 @@
+use std::cell::Cell;
+use std::io::{self, BufRead, Write};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::Instant;
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::crossterm::terminal;
+
+use fnaf::Keymap;
+
+// stdin is read on its own thread so the real-time clock never waits on
+// the keyboard, the menus just block on the next line. the thread only
+// reads when a line was asked for, so it never fights raw mode over keys
+pub struct Input {
+    requests: Sender<()>,
+    lines: Receiver<String>,
+    pending: Cell<bool>,
+    // moves are single keypresses instead of typed lines when set
+    keys: Option<Keymap>,
+}
+
+impl Input {
+    pub fn spawn(keys: Option<Keymap>) -> Input {
+        let (requests, asked) = mpsc::channel::<()>();
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            let mut stdin = std::io::stdin().lock();
+            for () in asked {
+                let mut line = String::new();
+                match stdin.read_line(&mut line) {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) => {}
+                }
+                if sender.send(line.trim_end().to_string()).is_err() {
+                    break;
+                }
+            }
+        });
+        Input {
+            requests,
+            lines,
+            pending: Cell::new(false),
+            keys,
+        }
+    }
+
+    fn request(&self) {
+        if !self.pending.replace(true) {
+            let _ = self.requests.send(());
+        }
+    }
+
+    pub fn read_line(&self, turn_input: &mut String) {
+        self.request();
+        match self.lines.recv() {
+            Ok(line) => {
+                self.pending.set(false);
+                *turn_input = line;
+            }
+            // stdin closed, nobody is left to play
+            Err(_) => std::process::exit(0),
+        }
+    }
+
+    // None once the deadline passed without a line
+    pub fn read_line_until(&self, deadline: Instant) -> Option<String> {
+        self.request();
+        let wait = deadline.saturating_duration_since(Instant::now());
+        match self.lines.recv_timeout(wait) {
+            Ok(line) => {
+                self.pending.set(false);
+                Some(line)
+            }
+            Err(RecvTimeoutError::Timeout) => None,
+            // nothing more is coming, the night goes on without the player
+            Err(RecvTimeoutError::Disconnected) => {
+                thread::sleep(wait);
+                None
+            }
+        }
+    }
+
+    pub fn keymap(&self) -> Option<&Keymap> {
+        self.keys.as_ref()
+    }
+
+    pub fn prompt(&self) -> String {
+        match &self.keys {
+            Some(keys) => {
+                format!("What is your move this turn? ({keys}, Enter to type one) : ")
+            }
+            None => String::from("What is your move this turn? : "),
+        }
+    }
+
+    // the next move as a command line, whichever way it was entered
+    pub fn read_move(&self) -> String {
+        match &self.keys {
+            // the terminal went away, nobody is left to play
+            Some(keys) => self
+                .read_key(keys, None)
+                .unwrap_or_else(|| std::process::exit(0)),
+            None => {
+                let mut line = String::new();
+                self.read_line(&mut line);
+                line
+            }
+        }
+    }
+
+    // None once the deadline passed without a move
+    pub fn read_move_until(&self, deadline: Instant) -> Option<String> {
+        match &self.keys {
+            // a line started with Enter is still being typed, raw mode
+            // would take the keys away from it
+            Some(_) if self.pending.get() => self.read_line_until(deadline),
+            Some(keys) => self.read_key(keys, Some(deadline)),
+            None => self.read_line_until(deadline),
+        }
+    }
+
+    // waits for one keypress in raw mode and turns it into the command it
+    // is bound to, an unbound key comes back as itself so it reads as
+    // invalid. Enter drops to a typed line for anything without a key, like
+    // "cam 2A"
+    fn read_key(&self, keys: &Keymap, deadline: Option<Instant>) -> Option<String> {
+        let _ = terminal::enable_raw_mode();
+        let key = next_key(deadline);
+        let _ = terminal::disable_raw_mode();
+
+        let key = key?;
+        if key.code == KeyCode::Esc
+            || (key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL))
+        {
+            std::process::exit(0);
+        }
+
+        let line = match key.code {
+            KeyCode::Enter => {
+                print!("> ");
+                let _ = io::stdout().flush();
+                return match deadline {
+                    Some(deadline) => self.read_line_until(deadline),
+                    None => {
+                        let mut line = String::new();
+                        self.read_line(&mut line);
+                        Some(line)
+                    }
+                };
+            }
+            KeyCode::Char(c) => match keys.command(c) {
+                Some(command) => command.to_string(),
+                None => c.to_string(),
+            },
+            _ => String::new(),
+        };
+        println!("> {line}");
+        Some(line)
+    }
+}
+
+fn next_key(deadline: Option<Instant>) -> Option<KeyEvent> {
+    loop {
+        if let Some(deadline) = deadline {
+            let wait = deadline.saturating_duration_since(Instant::now());
+            if !event::poll(wait).unwrap_or(false) {
+                return None;
+            }
+        }
+
+        match event::read() {
+            Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => return Some(key),
+            Ok(_) => {}
+            Err(_) => return None,
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use std::fmt;
+
+use crate::commands::Command;
+
+const DEFAULT_KEYMAP: &str = include_str!("../controls/keymap.toml");
+
+// single keypresses bound to commands, loaded from a keymap file
+#[derive(Clone, Debug)]
+pub struct Keymap {
+    pub bindings: Vec<(char, Command)>,
+}
+
+impl Keymap {
+    pub fn parse(text: &str) -> Result<Keymap, String> {
+        let table: toml::Table = toml::from_str(text).map_err(|err| err.to_string())?;
+        let mut bindings = Vec::new();
+
+        for (key, value) in table.iter() {
+            let key_char = match key.as_str() {
+                "space" => ' ',
+                _ => {
+                    let mut chars = key.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(c), None) => c.to_ascii_lowercase(),
+                        _ => return Err(format!("'{key}' is not a single key")),
+                    }
+                }
+            };
+
+            let command = value
+                .as_str()
+                .and_then(Command::parse)
+                .ok_or_else(|| format!("'{key}' is bound to {value}, which is not a command"))?;
+
+            if bindings.iter().any(|(bound, _)| *bound == key_char) {
+                return Err(format!("'{key}' is bound twice"));
+            }
+            bindings.push((key_char, command));
+        }
+
+        if bindings.is_empty() {
+            return Err(String::from("the keymap binds no keys"));
+        }
+
+        Ok(Keymap { bindings })
+    }
+
+    pub fn load(path: &str) -> Result<Keymap, String> {
+        let text = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+        Keymap::parse(&text)
+    }
+
+    pub fn command(&self, key: char) -> Option<Command> {
+        let key = key.to_ascii_lowercase();
+        self.bindings
+            .iter()
+            .find(|(bound, _)| *bound == key)
+            .map(|(_, command)| *command)
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Keymap {
+        Keymap::parse(DEFAULT_KEYMAP).expect("the built in keymap is valid")
+    }
+}
+
+// "Q left door, E right door, ..." for prompts and the explain screen
+impl fmt::Display for Keymap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (key, command)) in self.bindings.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            match key {
+                ' ' => write!(f, "Space {command}")?,
+                _ => write!(f, "{} {command}", key.to_ascii_uppercase())?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{CameraId, Side};
+
+    #[test]
+    fn single_keys_and_space_are_bound() {
+        let keys = Keymap::parse("q = \"left door\"\nspace = \"camera\"").unwrap();
+        assert_eq!(keys.command('q'), Some(Command::Door(Side::Left)));
+        assert_eq!(keys.command(' '), Some(Command::Camera));
+        assert_eq!(keys.command('e'), None);
+    }
+
+    #[test]
+    fn case_does_not_matter() {
+        let keys = Keymap::parse("Q = \"left door\"").unwrap();
+        assert_eq!(keys.bindings, vec![('q', Command::Door(Side::Left))]);
+        assert_eq!(keys.command('Q'), Some(Command::Door(Side::Left)));
+        assert_eq!(keys.command('q'), Some(Command::Door(Side::Left)));
+    }
+
+    #[test]
+    fn a_key_can_hold_any_typed_command() {
+        let keys = Keymap::parse("2 = \"cam 2A\"").unwrap();
+        assert_eq!(
+            keys.command('2'),
+            Some(Command::View(CameraId::parse("2A").unwrap()))
+        );
+    }
+
+    #[test]
+    fn bad_keymaps_are_errors() {
+        let err = Keymap::parse("q = \"left door\"\nQ = \"right door\"").unwrap_err();
+        assert!(err.contains("bound twice"), "{err}");
+
+        let err = Keymap::parse("qq = \"left door\"").unwrap_err();
+        assert!(err.contains("not a single key"), "{err}");
+
+        let err = Keymap::parse("q = \"dance\"").unwrap_err();
+        assert!(err.contains("not a command"), "{err}");
+        let err = Keymap::parse("q = 3").unwrap_err();
+        assert!(err.contains("not a command"), "{err}");
+
+        let err = Keymap::parse("").unwrap_err();
+        assert!(err.contains("binds no keys"), "{err}");
+    }
+
+    #[test]
+    fn the_built_in_keymap_loads() {
+        let keys = Keymap::default();
+        assert_eq!(keys.command(' '), Some(Command::Camera));
+        assert_eq!(keys.command('.'), Some(Command::Sit));
+    }
+}
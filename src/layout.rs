@@ -0,0 +1,256 @@
+use std::collections::{HashSet, VecDeque};
+
+use serde::Deserialize;
+
+use crate::commands::{CameraId, Side};
+use crate::map::Locations;
+
+const DEFAULT_LAYOUT: &str = include_str!("../maps/pizzeria.toml");
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Room {
+    pub id: Locations,
+    // placeholder name in the render template, {label}
+    pub label: String,
+    pub exits: Vec<Locations>,
+    // exits that are allowed to have no way back
+    #[serde(default)]
+    pub one_way: Vec<Locations>,
+    // what the camera calls it, the id is used when there is none
+    #[serde(default)]
+    pub name: Option<String>,
+    // rooms without one can't be looked at
+    #[serde(default)]
+    pub camera: Option<CameraId>,
+    // the camera has sound but no picture
+    #[serde(default)]
+    pub audio_only: bool,
+}
+
+impl Room {
+    pub fn name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("{:?}", self.id),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct Door {
+    pub side: Side,
+    pub hallway: Locations,
+    pub room: Locations,
+}
+
+// the static description of a facility, loaded from a map file
+#[derive(Clone, Debug, Deserialize)]
+pub struct Layout {
+    pub name: String,
+    pub spawn: Locations,
+    pub attack_room: Locations,
+    // rooms nobody walks into but a character can start in, like a stage
+    // behind a curtain
+    #[serde(default)]
+    pub hideouts: Vec<Locations>,
+    // where Golden Freddy can show up on camera, never without one
+    #[serde(default)]
+    pub hallucination_room: Option<Locations>,
+    // power lost every time the monitor changes to another camera
+    #[serde(default)]
+    pub camera_switch_power: u8,
+    pub template: String,
+    pub rooms: Vec<Room>,
+    pub doors: Vec<Door>,
+}
+
+impl Layout {
+    pub fn parse(text: &str) -> Result<Layout, String> {
+        let layout: Layout = toml::from_str(text).map_err(|err| err.to_string())?;
+        layout.validate()?;
+        Ok(layout)
+    }
+
+    pub fn load(path: &str) -> Result<Layout, String> {
+        let text = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+        Layout::parse(&text)
+    }
+
+    pub fn room(&self, location: Locations) -> Option<&Room> {
+        self.rooms.iter().find(|room| room.id == location)
+    }
+
+    pub fn find_adjacent_room(&self, location: Locations) -> Vec<Locations> {
+        match self.room(location) {
+            Some(room) => room.exits.clone(),
+            None => vec![location],
+        }
+    }
+
+    pub fn camera_room(&self, id: CameraId) -> Option<&Room> {
+        self.rooms.iter().find(|room| room.camera == Some(id))
+    }
+
+    // where the monitor starts out, the first room with a camera
+    pub fn first_camera(&self) -> Option<Locations> {
+        self.rooms
+            .iter()
+            .find(|room| room.camera.is_some())
+            .map(|room| room.id)
+    }
+
+    pub fn door(&self, side: Side) -> Option<&Door> {
+        self.doors.iter().find(|door| door.side == side)
+    }
+
+    // true for the rooms right behind a door, where you can hear breathing
+    pub fn is_door_room(&self, location: Locations) -> bool {
+        self.doors.iter().any(|door| door.room == location)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let mut seen = HashSet::new();
+        for room in self.rooms.iter() {
+            if !seen.insert(room.id) {
+                return Err(format!("room {:?} is defined twice", room.id));
+            }
+        }
+
+        if self.room(self.attack_room).is_none() {
+            return Err(format!("attack room {:?} is not a room", self.attack_room));
+        }
+        if self.room(self.spawn).is_none() {
+            return Err(format!("spawn {:?} is not a room", self.spawn));
+        }
+
+        for room in self.rooms.iter() {
+            if room.exits.is_empty() {
+                return Err(format!("room {:?} has no exits", room.id));
+            }
+
+            for exit in room.exits.iter() {
+                let Some(other) = self.room(*exit) else {
+                    return Err(format!(
+                        "room {:?} leads to unknown room {:?}",
+                        room.id, exit
+                    ));
+                };
+
+                if !other.exits.contains(&room.id) && !room.one_way.contains(exit) {
+                    return Err(format!(
+                        "{:?} leads to {:?} but not back, list it under one_way if that is on purpose",
+                        room.id, exit
+                    ));
+                }
+            }
+        }
+
+        for door in self.doors.iter() {
+            if self.doors.iter().filter(|d| d.side == door.side).count() > 1 {
+                return Err(format!("more than one {} door", door.side));
+            }
+
+            if !self.find_adjacent_room(door.hallway).contains(&door.room) {
+                return Err(format!(
+                    "{} door: {:?} does not lead to {:?}",
+                    door.side, door.hallway, door.room
+                ));
+            }
+
+            if !self
+                .find_adjacent_room(door.room)
+                .contains(&self.attack_room)
+            {
+                return Err(format!(
+                    "{} door: {:?} does not lead to the attack room",
+                    door.side, door.room
+                ));
+            }
+        }
+
+        for room in self.rooms.iter() {
+            let Some(camera) = room.camera else {
+                if room.audio_only {
+                    return Err(format!("{:?} is audio only but has no camera", room.id));
+                }
+                continue;
+            };
+
+            if self
+                .rooms
+                .iter()
+                .filter(|r| r.camera == Some(camera))
+                .count()
+                > 1
+            {
+                return Err(format!("more than one room on camera {camera}"));
+            }
+        }
+
+        if let Some(room) = self.hallucination_room {
+            match self.room(room) {
+                None => return Err(format!("hallucination room {:?} is not a room", room)),
+                Some(room) if room.camera.is_none() || room.audio_only => {
+                    return Err(format!(
+                        "hallucination room {:?} needs a camera with a picture",
+                        room.id
+                    ))
+                }
+                Some(_) => {}
+            }
+        }
+
+        for hideout in self.hideouts.iter() {
+            if self.room(*hideout).is_none() {
+                return Err(format!("hideout {:?} is not a room", hideout));
+            }
+        }
+
+        // everything has to be reachable from the spawn or a hideout,
+        // otherwise nobody will ever walk into it
+        let mut reachable = self.distances_from(self.spawn);
+        for hideout in self.hideouts.iter() {
+            reachable.extend(self.distances_from(*hideout));
+        }
+        for room in self.rooms.iter() {
+            if !reachable.iter().any(|(location, _)| *location == room.id) {
+                return Err(format!(
+                    "room {:?} can't be reached from the spawn or a hideout",
+                    room.id
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn distance(&self, from: Locations, to: Locations) -> Option<u8> {
+        self.distances_from(from)
+            .into_iter()
+            .find(|(location, _)| *location == to)
+            .map(|(_, distance)| distance)
+    }
+
+    // breadth first search over the exits
+    pub fn distances_from(&self, start: Locations) -> Vec<(Locations, u8)> {
+        let mut distances = vec![(start, 0)];
+        let mut queue = VecDeque::from([(start, 0)]);
+
+        while let Some((location, distance)) = queue.pop_front() {
+            for exit in self.find_adjacent_room(location) {
+                if !distances.iter().any(|(seen, _)| *seen == exit) {
+                    distances.push((exit, distance + 1));
+                    queue.push_back((exit, distance + 1));
+                }
+            }
+        }
+
+        distances
+    }
+}
+
+impl Default for Layout {
+    fn default() -> Layout {
+        Layout::parse(DEFAULT_LAYOUT).expect("the built in map is valid")
+    }
+}
@@ -0,0 +1,42 @@
+pub mod animatronics;
+pub mod behavior;
+pub mod blackout;
+pub mod campaign;
+pub mod clock;
+pub mod commands;
+pub mod extras;
+pub mod golden_freddy;
+pub mod keymap;
+pub mod layout;
+pub mod map;
+pub mod night;
+pub mod power;
+pub mod replay;
+pub mod roster;
+pub mod save;
+pub mod simulate;
+pub mod strategy;
+
+pub use animatronics::{Animatronic, Tells};
+pub use behavior::{
+    AiMode, Behavior, CameraShy, DoorCamper, GoalSeeking, Move, RandomWalk, WorldView,
+};
+pub use blackout::{Blackout, PowerOut};
+pub use campaign::{Campaign, RampStep};
+pub use clock::Clock;
+pub use commands::{Command, Side};
+pub use extras::{Extras, Preset};
+pub use golden_freddy::{GoldenFreddy, Hallucination};
+pub use keymap::Keymap;
+pub use layout::{Door, Layout, Room};
+pub use map::{Locations, Map};
+pub use night::{Night, TickOutcome};
+pub use power::{Battery, PowerDraw};
+pub use replay::{Ending, NightRecord, Replay};
+pub use roster::{Profile, Roster};
+pub use save::{Save, Saves};
+pub use simulate::Summary;
+pub use strategy::{
+    Bot, CameraEveryOtherTurn, CloseOnBreathing, Observation, PowerBudget, ShutDoors, SitForever,
+    Strategy,
+};
@@ -1,1172 +1,870 @@
-use rand::Rng;
-
-use std::cmp;
-
-#[derive(PartialEq, Hash, Clone, Copy, Eq)]
-enum Locations {
-    ShowStage,
-    DiningAreaL,
-    DiningAreaR,
-    DiningAreaC,
-    Restrooms,
-    Kitchen,
-    Arcade,
-    SecurityOfficeStaticR,
-    SecurityOfficeStaticL,
-    SecurityOfficeAttack,
-    HallwayL,
-    HallwayR,
+use std::io::IsTerminal;
+use std::time::{Duration, Instant};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+mod input;
+mod tui;
+
+use input::Input;
+
+use fnaf::clock::tick_duration;
+use fnaf::extras::animatronics_at;
+use fnaf::save::DEFAULT_SLOT;
+use fnaf::simulate::simulate;
+use fnaf::{
+    AiMode, Animatronic, Bot, Campaign, Ending, Extras, Keymap, Layout, Night, NightRecord,
+    RampStep, Replay, Roster, Save, Saves, TickOutcome,
+};
+
+const USAGE: &str = "Usage: fnaf [--seed <number>] [--night <number>] [--ai classic|hunt|camper|camera-shy] [--map <file>] [--roster <file>] [--record <file>] [--replay <file>] [--realtime] [--hour-seconds <number>] [--tui] [--keymap <file>] [--typed] [--slot <name>] [--save-dir <dir>] [--extras <file>] [--campaign <file>] [--debug]\n       fnaf simulate [--nights <number>] [--levels <n/n/...>]... [--strategy sit|doors|breathing|camera|budget] [--seed <number>] [--ai <mode>] [--map <file>] [--roster <file>]";
+
+// nights played for every set of levels when --nights is left out
+const DEFAULT_SIM_NIGHTS: u32 = 1000;
+
+// the original is around a minute and a half per hour
+const DEFAULT_HOUR_SECONDS: f64 = 90.0;
+
+struct Options {
+    seed: Option<u64>,
+    // New Game starts the campaign on this night, as a practice run
+    night: Option<u8>,
+    ai: AiMode,
+    map: Option<String>,
+    roster: Option<String>,
+    record: Option<String>,
+    replay: Option<String>,
+    // clock runs on its own instead of one tick per command
+    realtime: bool,
+    hour_seconds: f64,
+    tui: bool,
+    keymap: Option<String>,
+    // type whole commands even on a terminal
+    typed: bool,
+    slot: String,
+    save_dir: Option<String>,
+    extras: Option<String>,
+    campaign: Option<String>,
+    // show AI level changes during the night
+    debug: bool,
+    // play nights headlessly and print statistics instead of the game
+    simulate: bool,
+    nights: u32,
+    // one set of AI levels per batch, in roster order
+    levels: Vec<Vec<u8>>,
+    strategy: Bot,
 }
 
-#[derive(PartialEq, Hash, Clone, Copy, Eq)]
-enum Tells {
-    Laughing,
-    Noise,
-    Footsteps,
-    Static,
-    Visual,
-    Breathing,
+fn usage_error(message: String) -> ! {
+    eprintln!("{message}\n{USAGE}");
+    std::process::exit(1);
 }
 
-impl Tells {
-    fn value(&self) -> &str {
-        match self {
-            Tells::Laughing => "l",
-            Tells::Noise => "n",
-            Tells::Footsteps => "f",
-            Tells::Static => "s",
-            Tells::Visual => "v",
-            Tells::Breathing => "b",
+fn parse_args() -> Options {
+    let mut options = Options {
+        seed: None,
+        night: None,
+        ai: AiMode::Classic,
+        map: None,
+        roster: None,
+        record: None,
+        replay: None,
+        realtime: false,
+        hour_seconds: DEFAULT_HOUR_SECONDS,
+        tui: false,
+        keymap: None,
+        typed: false,
+        slot: String::from(DEFAULT_SLOT),
+        save_dir: None,
+        extras: None,
+        campaign: None,
+        debug: false,
+        simulate: false,
+        nights: DEFAULT_SIM_NIGHTS,
+        levels: Vec::new(),
+        strategy: Bot::Sit,
+    };
+    let mut args = std::env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .unwrap_or_else(|| usage_error(format!("{arg} needs a value")))
+        };
+
+        match arg.as_str() {
+            "--seed" => match value().parse::<u64>() {
+                Ok(seed) => options.seed = Some(seed),
+                Err(_) => usage_error(String::from("--seed needs a number")),
+            },
+            "--night" => match value().parse::<u8>() {
+                Ok(night) if night > 0 => options.night = Some(night),
+                _ => usage_error(String::from("--night needs a night number, 1 or more")),
+            },
+            "--ai" => match AiMode::parse(&value()) {
+                Some(ai) => options.ai = ai,
+                None => usage_error(String::from(
+                    "--ai is one of classic, hunt, camper or camera-shy",
+                )),
+            },
+            "--map" => options.map = Some(value()),
+            "--roster" => options.roster = Some(value()),
+            "--record" => options.record = Some(value()),
+            "--replay" => options.replay = Some(value()),
+            "--realtime" => options.realtime = true,
+            "--tui" => options.tui = true,
+            "--keymap" => options.keymap = Some(value()),
+            "--typed" => options.typed = true,
+            "--slot" => {
+                options.slot = value();
+                if !Saves::valid_slot(&options.slot) {
+                    usage_error(String::from("--slot names use letters, numbers, - and _"));
+                }
+            }
+            "--save-dir" => options.save_dir = Some(value()),
+            "--extras" => options.extras = Some(value()),
+            "--campaign" => options.campaign = Some(value()),
+            "--debug" => options.debug = true,
+            "simulate" => options.simulate = true,
+            "--nights" => match value().parse::<u32>() {
+                Ok(nights) if nights > 0 => options.nights = nights,
+                _ => usage_error(String::from("--nights needs a positive number")),
+            },
+            "--levels" => {
+                let levels: Result<Vec<u8>, _> = value()
+                    .split('/')
+                    .map(|level| level.trim().parse::<u8>())
+                    .collect();
+                match levels {
+                    Ok(levels) => options.levels.push(levels),
+                    Err(_) => usage_error(String::from(
+                        "--levels needs AI levels split by '/', like 5/3/3/2",
+                    )),
+                }
+            }
+            "--strategy" => match Bot::parse(&value()) {
+                Some(strategy) => options.strategy = strategy,
+                None => usage_error(String::from(
+                    "--strategy is one of sit, doors, breathing, camera or budget",
+                )),
+            },
+            "--hour-seconds" => match value().parse::<f64>() {
+                Ok(seconds) if seconds > 0.0 && seconds.is_finite() => {
+                    options.hour_seconds = seconds;
+                    options.realtime = true;
+                }
+                _ => usage_error(String::from("--hour-seconds needs a positive number")),
+            },
+            "--help" | "-h" => {
+                println!("{USAGE}");
+                std::process::exit(0);
+            }
+            _ => usage_error(format!("Unknown argument '{arg}'")),
         }
     }
-}
 
-#[derive(Clone, Copy)]
-enum PowerDraw {
-    Camera,
-    Lights,
-    Doors,
+    options
 }
 
-impl PowerDraw {
-    fn value(&self) -> i8 {
-        match self {
-            PowerDraw::Camera => 2,
-            PowerDraw::Lights => 4,
-            PowerDraw::Doors => 7,
-        }
-    }
+// writes every finished night to the --record file as it happens, so a
+// crash or ctrl-c still leaves the nights played so far on disk
+struct Recorder {
+    path: String,
+    replay: Replay,
 }
 
-impl PartialEq for PowerDraw {
-    fn eq(&self, other: &Self) -> bool {
-        *self as i8 == *other as i8
+impl Recorder {
+    fn save(&mut self, night: &Night, outcome: &TickOutcome) {
+        let ending = Ending::from_outcome(outcome, night.clock);
+        self.replay
+            .nights
+            .push(NightRecord::from_night(night, ending));
+
+        if let Err(err) = std::fs::write(&self.path, self.replay.to_string()) {
+            eprintln!("Could not write replay to {}: {err}", self.path);
+        }
     }
 }
 
-struct Battery {
-    power: i8,
-    power_draw: Vec<PowerDraw>,
-    is_online: bool,
+// the campaign of the selected save slot, kept on disk after every night
+// unless there is nowhere to put it
+struct Progress {
+    saves: Option<Saves>,
+    slot: String,
+    save: Save,
 }
 
-impl Battery {
-    fn new() -> Battery {
-        Battery {
-            power: 100,
-            power_draw: Vec::new(),
-            is_online: true,
-        }
+impl Progress {
+    fn load(saves: Option<Saves>, slot: String) -> Progress {
+        let save = match &saves {
+            Some(saves) => saves.load(&slot).unwrap_or_else(|err| {
+                eprintln!("Could not read save slot {slot}: {err}");
+                Save::default()
+            }),
+            None => Save::default(),
+        };
+
+        Progress { saves, slot, save }
     }
 
-    fn add_power_draw(&mut self, power_draw: PowerDraw) {
-        if self.is_online == false {
-            return;
-        }
-
-        let index = self.power_draw.iter().position(|&x| x == power_draw);
-        match index {
-            Some(_) => {}
-            None => {
-                self.power_draw.push(power_draw);
+    fn store(&self) {
+        if let Some(saves) = &self.saves {
+            if let Err(err) = saves.store(&self.slot, &self.save) {
+                eprintln!("Could not write save slot {}: {err}", self.slot);
             }
         }
     }
 
-    fn remove_power_draw(&mut self, power_draw: PowerDraw) {
-        if self.is_online == false {
-            return;
-        }
-
-        let index = self.power_draw.iter().position(|&x| x == power_draw);
-        match index {
-            Some(i) => {
-                self.power_draw.remove(i);
-            }
-            None => {}
-        }
+    fn switch(&mut self, slot: String) {
+        let saves = self.saves.take();
+        *self = Progress::load(saves, slot);
     }
+}
 
-    fn update_power(&mut self) {
-        if self.is_online == false {
-            return;
-        }
+fn describe_save(save: &Save) -> String {
+    let mut parts = Vec::new();
+    if save.in_progress() {
+        parts.push(format!("on Night {}", save.night));
+    }
+    parts.push(format!("{} nights completed", save.nights_completed));
+    if save.stars() > 0 {
+        parts.push("*".repeat(save.stars() as usize));
+    }
+    if save.custom_night {
+        parts.push(String::from("Custom Night unlocked"));
+    }
+    parts.join(", ")
+}
 
-        let mut rng = rand::thread_rng();
-        for (_i, &draw) in self.power_draw.iter().enumerate() {
-            let random_tick: u8 = rng.gen_range(1..20);
-            if draw as u8 * 2 <= random_tick {
-                if self.power > 0 {
-                    self.power -= draw.value();
-                } else {
-                    self.power = 0;
-                }
-            }
-        }
+fn print_stars(save: &Save) {
+    if save.stars() == 0 {
+        return;
     }
 
-    fn shutdown(&mut self) {
-        self.power = -1;
-        self.power_draw.clear();
-        self.is_online = false;
+    // what each star on the main menu was given for
+    let stars = [
+        (save.five_nights, "survived the five nights"),
+        (save.night_six_beaten, "survived Night 6"),
+        (save.max_custom, "survived a Custom Night at 20/20/20/20"),
+    ];
+
+    println!("Stars: {}", "*".repeat(save.stars() as usize));
+    for (_, star) in stars.iter().filter(|(earned, _)| *earned) {
+        println!("\t * {star}");
     }
 }
 
-struct Animatronic {
-    name: String,
-    location: Locations,
-    difficulty: u8,
-    current_tell: Tells,
+fn describe_levels(anims: &[Animatronic]) -> String {
+    anims
+        .iter()
+        .map(|anim| format!("{} {}", anim.name, anim.difficulty))
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
-impl Animatronic {
-    fn new(name: String, location: Locations, difficulty: u8) -> Animatronic {
-        let clamped = cmp::min::<u8>(difficulty, 20);
-
-        Animatronic {
-            name,
-            location,
-            difficulty: clamped,
-            current_tell: Tells::Visual,
-        }
+// the custom night cast, from a preset or typed in one level at a time
+fn pick_custom_night(roster: &Roster, extras: &Extras, input: &Input) -> Option<Vec<Animatronic>> {
+    println!("Custom Night presets: ");
+    for (number, preset) in extras.presets.iter().enumerate() {
+        let anims = animatronics_at(&preset.levels, roster);
+        println!(
+            "\t {} -- {} ({})",
+            number + 1,
+            preset.name,
+            describe_levels(&anims)
+        );
     }
-
-    fn find_adjacent_room(&mut self) -> Vec<Locations> {
-        let mut ret: Vec<Locations> = Vec::new();
-
-        // lol made by a map that i drew up
-        // why do things automatically when you can hard code them!
-
-        /*
-         Map btw
-                                                       [Show Stage]
-           [Arcade]   <===  [Dining Area L]    <===   [Dining Area C]    ===>   [Dining Area R]  ===>  [Kitchen]
-           [Restrooms]----------------------------------[Restrooms]                                   [Hallway R]
-           [Hallway L]                                                                                [Hallway R]
-           [Hallway L] [Security Office Static R] [Security Office Attack] [Security Office Static R] [Hallway R]
-        */
-
-        match self.location {
-            Locations::HallwayL => {
-                ret.push(Locations::Restrooms);
-                ret.push(Locations::SecurityOfficeStaticL);
-            }
-            Locations::HallwayR => {
-                ret.push(Locations::Kitchen);
-                ret.push(Locations::SecurityOfficeStaticR);
-            }
-            Locations::ShowStage => {
-                ret.push(Locations::DiningAreaC);
-            }
-            Locations::DiningAreaL => {
-                ret.push(Locations::Arcade);
-                ret.push(Locations::DiningAreaC);
-            }
-            Locations::DiningAreaC => {
-                ret.push(Locations::DiningAreaL);
-                ret.push(Locations::DiningAreaR);
-                ret.push(Locations::ShowStage);
-                ret.push(Locations::Restrooms);
-            }
-            Locations::DiningAreaR => {
-                ret.push(Locations::DiningAreaC);
-                ret.push(Locations::Kitchen);
-            }
-            Locations::Restrooms => {
-                ret.push(Locations::DiningAreaC);
-                ret.push(Locations::HallwayL);
-                ret.push(Locations::Arcade);
-            }
-            Locations::Kitchen => {
-                ret.push(Locations::DiningAreaR);
-                ret.push(Locations::HallwayR);
-            }
-            Locations::Arcade => {
-                ret.push(Locations::Restrooms);
-                ret.push(Locations::DiningAreaL);
-                ret.push(Locations::HallwayL);
-            }
-            Locations::SecurityOfficeStaticR => {
-                ret.push(Locations::SecurityOfficeAttack);
-            }
-            Locations::SecurityOfficeStaticL => {
-                ret.push(Locations::SecurityOfficeAttack);
-            }
-            Locations::SecurityOfficeAttack => {
-                ret.push(self.location);
-            }
+    println!("\t Manual -- pick every level yourself");
+
+    let mut line = String::new();
+    input.read_line(&mut line);
+    let choice = line.trim();
+
+    if choice.is_empty() || choice.eq_ignore_ascii_case("manual") {
+        let mut animatronics = Vec::new();
+        for profile in roster.characters.iter() {
+            println!("Please enter the difficulty for {}: ", profile.name);
+            input.read_line(&mut line);
+            let difficulty: u8 = line.trim().parse::<u8>().unwrap_or(profile.difficulty);
+            animatronics.push(Animatronic::from_profile(profile.clone(), difficulty));
         }
-
-        ret
+        return Some(animatronics);
     }
 
-    fn move_tick(
-        &mut self,
-        adjacent_loc: Vec<Locations>,
-        right_door_closed: bool,
-        left_door_closed: bool,
-    ) {
-        if right_door_closed && self.location == Locations::SecurityOfficeStaticR {
-            self.location = Locations::HallwayR;
+    let preset = match choice.parse::<usize>() {
+        Ok(number) => number
+            .checked_sub(1)
+            .and_then(|index| extras.presets.get(index)),
+        Err(_) => extras.find(choice),
+    };
+    match preset {
+        Some(preset) => {
+            println!("{}", preset.name);
+            Some(animatronics_at(&preset.levels, roster))
         }
-
-        if left_door_closed && self.location == Locations::SecurityOfficeStaticL {
-            self.location = Locations::HallwayL;
+        None => {
+            println!("There is no preset '{choice}'.\n");
+            None
         }
+    }
+}
 
-        // move the animatronic
-        let mut rng = rand::thread_rng();
-        let random_index = rng.gen_range(0..20);
-
-        if random_index <= self.difficulty {
-            {
-                // move the animatronic
-                let adjacent_rooms = adjacent_loc;
-                // let mut closest: Locations = Locations::ShowStage;
-
-                let random_index = rng.gen_range(0..adjacent_rooms.len());
-
-                // Check to see if the animatronic is trying to move to the security office
-                // if it is, check if the door is closed
-                // if it is, don't move there
-                if adjacent_rooms[random_index] == Locations::SecurityOfficeStaticR
-                    && right_door_closed
-                {
-                    return;
-                }
-
-                if adjacent_rooms[random_index] == Locations::SecurityOfficeStaticL
-                    && left_door_closed
-                {
-                    return;
-                }
+// a night outside the campaign, Night 6 or a Custom Night
+fn play_single_night(
+    title: &str,
+    animatronics: Vec<Animatronic>,
+    ramp: Vec<RampStep>,
+    layout: &Layout,
+    input: &Input,
+    options: &Options,
+    recorder: &mut Option<Recorder>,
+) -> (Night, TickOutcome) {
+    let seed = options.seed.unwrap_or_else(rand::random);
+    let mut night = Night::new(layout.clone(), animatronics, seed);
+    night.map.set_ai(options.ai);
+    night.ramp = ramp;
+    night.set_debug(options.debug);
+
+    println!("Dusk of {title}");
+    let outcome = play_night(&mut night, title, input, options);
+    if let Some(recorder) = recorder.as_mut() {
+        recorder.save(&night, &outcome);
+    }
+    println!("Seed: {seed}\n");
 
-                self.location = adjacent_rooms[random_index];
-            }
+    (night, outcome)
+}
 
-            {
-                // set the tell
-                let random_tell = rng.gen_range(0..5);
-                match random_tell {
-                    0 => {
-                        self.current_tell = Tells::Laughing;
-                    }
-                    1 => {
-                        self.current_tell = Tells::Noise;
-                    }
-                    2 => {
-                        self.current_tell = Tells::Footsteps;
-                    }
-                    3 => {
-                        self.current_tell = Tells::Static;
-                    }
-                    4 => {
-                        self.current_tell = Tells::Visual;
-                    }
-                    _ => {
-                        self.current_tell = Tells::Visual;
-                    }
-                }
+fn manage_slots(progress: &mut Progress, input: &Input) {
+    let Some(saves) = &progress.saves else {
+        println!("There is nowhere to keep saves, use --save-dir to pick a place.\n");
+        return;
+    };
+
+    println!("Save slots in {}: ", saves.dir.display());
+    let mut slots = saves.slots();
+    if !slots.contains(&progress.slot) {
+        slots.push(progress.slot.clone());
+        slots.sort();
+    }
+    for slot in slots {
+        let save = if slot == progress.slot {
+            progress.save.clone()
+        } else {
+            saves.load(&slot).unwrap_or_default()
+        };
+        let current = if slot == progress.slot {
+            " (current)"
+        } else {
+            ""
+        };
+        println!("\t {slot}{current} -- {}", describe_save(&save));
+    }
+    println!("Type a slot name to switch to it (a new name starts a new slot), delete <name> to remove one, or back.");
 
-                if self.name == "Freddy" && self.current_tell == Tells::Noise {
-                    self.current_tell = Tells::Laughing;
-                } else if self.current_tell == Tells::Laughing {
-                    self.current_tell = Tells::Noise;
-                }
+    let mut line = String::new();
+    input.read_line(&mut line);
+    let line = line.trim();
+    let lower = line.to_lowercase();
 
-                if self.name == "Chica" && self.location == Locations::Kitchen && random_tell > 2 {
-                    self.current_tell = Tells::Static;
-                }
+    if line.is_empty() || lower == "back" {
+        return;
+    }
 
-                if self.location == Locations::SecurityOfficeStaticL
-                    || self.location == Locations::SecurityOfficeStaticR
-                {
-                    self.current_tell = Tells::Breathing;
+    // slot names keep their case, only the word delete doesn't care
+    if lower.starts_with("delete ") {
+        let deleted = String::from(line["delete ".len()..].trim());
+        match saves.delete(&deleted) {
+            Ok(()) => {
+                println!("Deleted slot {deleted}.\n");
+                if deleted == progress.slot {
+                    progress.save = Save::default();
                 }
             }
+            Err(err) => println!("Could not delete slot {deleted}: {err}\n"),
         }
+        return;
     }
-}
 
-struct Map {
-    grid: [[Option<Locations>; 9]; 5],
-    left_door_closed: bool,
-    right_door_closed: bool,
-    left_light_on: bool,
-    right_light_on: bool,
-    anim_states: Vec<Animatronic>,
-    is_dead: bool,
-    killer: String,
+    if !Saves::valid_slot(line) {
+        println!("Slot names use letters, numbers, - and _.\n");
+        return;
+    }
+    progress.switch(String::from(line));
+    println!("Now playing on slot {}.\n", progress.slot);
 }
 
-impl Map {
-    fn new() -> Map {
-        Map {
-            grid: [
-                [
-                    None,
-                    None,
-                    None,
-                    Some(Locations::ShowStage),
-                    Some(Locations::ShowStage),
-                    Some(Locations::ShowStage),
-                    None,
-                    None,
-                    None,
-                ],
-                [
-                    None,
-                    None,
-                    Some(Locations::Arcade),
-                    Some(Locations::DiningAreaL),
-                    Some(Locations::DiningAreaC),
-                    Some(Locations::DiningAreaR),
-                    Some(Locations::Kitchen),
-                    None,
-                    None,
-                ],
-                [
-                    None,
-                    None,
-                    Some(Locations::Restrooms),
-                    Some(Locations::Restrooms),
-                    Some(Locations::Restrooms),
-                    Some(Locations::Restrooms),
-                    Some(Locations::HallwayR),
-                    None,
-                    None,
-                ],
-                [
-                    None,
-                    None,
-                    Some(Locations::HallwayL),
-                    None,
-                    None,
-                    None,
-                    Some(Locations::HallwayR),
-                    None,
-                    None,
-                ],
-                [
-                    None,
-                    None,
-                    Some(Locations::HallwayL),
-                    Some(Locations::SecurityOfficeStaticR),
-                    Some(Locations::SecurityOfficeAttack),
-                    Some(Locations::SecurityOfficeStaticR),
-                    Some(Locations::HallwayR),
-                    None,
-                    None,
-                ],
-            ],
-            left_door_closed: false,
-            right_door_closed: false,
-            left_light_on: false,
-            right_light_on: false,
-            anim_states: Vec::new(),
-            is_dead: false,
-            killer: String::from("MissingNo."),
+// plays the saved campaign from its current night until a death or dawn of
+// the last night, the save follows along after every night
+fn play_campaign(
+    progress: &mut Progress,
+    campaign: &Campaign,
+    layout: &Layout,
+    roster: &Roster,
+    input: &Input,
+    options: &Options,
+    recorder: &mut Option<Recorder>,
+) {
+    // each night gets its own seed drawn from the campaign rng, which starts
+    // over from the saved seed every night so Continue plays the same nights
+    let mut campaign_rng = StdRng::seed_from_u64(progress.save.seed);
+
+    loop {
+        let night_number = progress.save.night;
+        // what --seed and --night need to play this night again
+        let seed = progress.save.seed;
+        let animatronics = campaign.animatronics(night_number, roster);
+        let mut night = Night::new(layout.clone(), animatronics, campaign_rng.random());
+        night.map.set_ai(options.ai);
+        night.ramp = campaign.ramp(night_number);
+        night.set_debug(options.debug);
+
+        let title = format!("Night {night_number}");
+        println!("Dusk of {title}");
+        let outcome = play_night(&mut night, &title, input, options);
+        if let Some(recorder) = recorder.as_mut() {
+            recorder.save(&night, &outcome);
         }
-    }
 
-    fn night_reset(&mut self) {
-        // reset the map
-        self.left_door_closed = false;
-        self.right_door_closed = false;
-        self.left_light_on = false;
-        self.right_light_on = false;
-        self.is_dead = false;
-        self.killer = String::from("MissingNo.");
-
-        for (_i, anim) in self.anim_states.iter_mut().enumerate() {
-            anim.location = Locations::ShowStage;
-            anim.current_tell = Tells::Visual;
+        if let TickOutcome::Killed(_) = outcome {
+            // the save stays on this night for Continue
+            println!("Seed: {seed} (Night {night_number}, play it again with --seed {seed} --night {night_number})\n");
+            break;
         }
-    }
 
-    fn map_tick(&mut self) {
-        for (_i, anim) in self.anim_states.iter_mut().enumerate() {
-            let locations = anim.find_adjacent_room();
-            anim.move_tick(locations, self.right_door_closed, self.left_door_closed);
+        progress.save.complete_night(night_number, campaign.len());
+        if night_number >= campaign.len() {
+            progress.store();
+            println!(
+                "You survived all {} nights! Congratulations! \n",
+                campaign.len()
+            );
+            println!("Night 6 and Custom Night are unlocked.\n");
+            println!("Seed: {seed} (Night {night_number}, play it again with --seed {seed} --night {night_number})\n");
+            break;
+        }
 
-            if anim.location == Locations::SecurityOfficeAttack {
-                self.is_dead = true;
-                self.killer = anim.name.clone();
-            }
+        let next = campaign_rng.random();
+        campaign_rng = StdRng::seed_from_u64(next);
+        progress.save.start_night(night_number + 1, next);
+        progress.store();
+    }
+}
 
-            if anim.location == Locations::HallwayL && self.left_light_on {
-                println!("You see {} is at the left door!", anim.name);
-            }
+fn run_simulation(layout: &Layout, roster: &Roster, options: &Options) {
+    let batches = if options.levels.is_empty() {
+        vec![roster
+            .characters
+            .iter()
+            .map(|profile| profile.difficulty)
+            .collect()]
+    } else {
+        options.levels.clone()
+    };
+
+    for levels in batches.iter() {
+        if levels.len() != roster.characters.len() {
+            eprintln!(
+                "--levels {} needs one level for each of the {} characters in the roster",
+                levels
+                    .iter()
+                    .map(|level| level.to_string())
+                    .collect::<Vec<_>>()
+                    .join("/"),
+                roster.characters.len()
+            );
+            std::process::exit(1);
+        }
+    }
 
-            if anim.location == Locations::HallwayR && self.right_light_on {
-                println!("You see {} is at the right door!", anim.name);
-            }
+    let seed = options.seed.unwrap_or_else(rand::random);
+    println!(
+        "Simulating {} nights per setting with the {} strategy, seed {seed}\n",
+        options.nights, options.strategy
+    );
+
+    for levels in batches.iter() {
+        let summary = simulate(
+            layout,
+            roster,
+            levels,
+            options.ai,
+            options.strategy,
+            options.nights,
+            seed,
+        );
+        println!("{summary}\n");
+    }
+}
+
+fn run_replay(path: &str, layout: &Layout, roster: &Roster) -> bool {
+    let replay = match std::fs::read_to_string(path)
+        .map_err(|err| err.to_string())
+        .and_then(|text| Replay::parse(&text))
+    {
+        Ok(replay) => replay,
+        Err(err) => {
+            eprintln!("Could not load replay {path}: {err}");
+            return false;
         }
+    };
+
+    if replay.version != env!("CARGO_PKG_VERSION") {
+        println!(
+            "Note: replay was recorded with version {}, this is {}",
+            replay.version,
+            env!("CARGO_PKG_VERSION")
+        );
     }
 
-    fn find_adjacent_room(&mut self, location: Locations) -> Vec<Locations> {
-        let mut ret: Vec<Locations> = Vec::new();
-
-        // lol made by a map that i drew up
-        // why do things automatically when you can hard code them!
-
-        /*
-         Map btw
-                                                       [Show Stage]
-           [Arcade]   <===  [Dining Area L]    <===   [Dining Area C]    ===>   [Dining Area R]  ===>  [Kitchen]
-           [Restrooms]----------------------------------[Restrooms]                                   [Hallway R]
-           [Hallway L]                                                                                [Hallway R]
-           [Hallway L] [Security Office Static R] [Security Office Attack] [Security Office Static R] [Hallway R]
-        */
-
-        match location {
-            Locations::HallwayL => {
-                ret.push(Locations::Restrooms);
-                ret.push(Locations::SecurityOfficeStaticL);
+    let mut all_match = true;
+    for (i, night) in replay.nights.iter().enumerate() {
+        let number = i + 1;
+        match (night.run(layout, roster), &night.ending) {
+            (Ok(ending), Some(expected)) if ending == *expected => {
+                println!("Night {number}: {ending} (matches)");
             }
-            Locations::HallwayR => {
-                ret.push(Locations::Kitchen);
-                ret.push(Locations::SecurityOfficeStaticR);
+            (Ok(ending), Some(expected)) => {
+                println!("Night {number}: {ending}, but the recording says {expected}");
+                all_match = false;
             }
-            Locations::ShowStage => {
-                ret.push(Locations::DiningAreaC);
+            (Ok(ending), None) => {
+                println!("Night {number}: {ending} (no ending was recorded)");
             }
-            Locations::DiningAreaL => {
-                ret.push(Locations::Arcade);
-                ret.push(Locations::DiningAreaC);
-            }
-            Locations::DiningAreaC => {
-                ret.push(Locations::DiningAreaL);
-                ret.push(Locations::DiningAreaR);
-                ret.push(Locations::ShowStage);
-                ret.push(Locations::Restrooms);
-            }
-            Locations::DiningAreaR => {
-                ret.push(Locations::DiningAreaC);
-                ret.push(Locations::Kitchen);
-            }
-            Locations::Restrooms => {
-                ret.push(Locations::DiningAreaC);
-                ret.push(Locations::HallwayL);
-                ret.push(Locations::Arcade);
-            }
-            Locations::Kitchen => {
-                ret.push(Locations::DiningAreaR);
-                ret.push(Locations::HallwayR);
-            }
-            Locations::Arcade => {
-                ret.push(Locations::Restrooms);
-                ret.push(Locations::DiningAreaL);
-                ret.push(Locations::HallwayL);
-            }
-            Locations::SecurityOfficeStaticR => {
-                ret.push(Locations::SecurityOfficeAttack);
-            }
-            Locations::SecurityOfficeStaticL => {
-                ret.push(Locations::SecurityOfficeAttack);
-            }
-            Locations::SecurityOfficeAttack => {
-                ret.push(location);
+            (Err(err), _) => {
+                println!("Night {number}: replay failed, {err}");
+                all_match = false;
             }
         }
+    }
+
+    all_match
+}
 
-        ret
+fn print_messages(night: &mut Night) {
+    for view in night.take_camera_views() {
+        println!("{view}");
+    }
+    for message in night.take_messages() {
+        println!("{message}");
     }
+}
 
-    fn find_location(&self, location: &Locations) -> (u8, u8) {
-        for (i, row) in self.grid.iter().enumerate() {
-            for (j, loc) in row.iter().enumerate() {
-                match loc {
-                    Some(l) => {
-                        if *l == *location {
-                            return (i as u8, j as u8);
-                        }
-                    }
-                    None => {}
-                }
-            }
+fn night_over(night: &Night, outcome: &TickOutcome) -> bool {
+    match outcome {
+        TickOutcome::Continue => false,
+        TickOutcome::Survived => {
+            println!("You survived the night! Congratulations! \n");
+            true
+        }
+        TickOutcome::Killed(_) => {
+            println!("{}", night.death_message());
+            true
         }
-        (0, 0)
     }
+}
 
-    fn distance_from_office_attack(&self, location: &Locations) -> u8 {
-        let office_attack = &Locations::SecurityOfficeAttack;
-        let (x1, y1) = self.find_location(office_attack);
-        let (x2, y2) = self.find_location(location);
+fn play_night(night: &mut Night, title: &str, input: &Input, options: &Options) -> TickOutcome {
+    let tick_length = options
+        .realtime
+        .then(|| tick_duration(options.hour_seconds));
+
+    if options.tui {
+        let outcome =
+            tui::play_night(night, title, tick_length, input.keymap()).unwrap_or_else(|err| {
+                eprintln!("The terminal UI failed: {err}");
+                std::process::exit(1);
+            });
+        night_over(night, &outcome);
+        return outcome;
+    }
 
-        let distance =
-            ((x1 as f64 - x2 as f64).powf(2.0) + (y1 as f64 - y2 as f64).powf(2.0)) as f64;
-        distance.sqrt() as u8
+    match tick_length {
+        Some(tick_length) => play_night_realtime(night, input, tick_length),
+        None => play_night_turns(night, input),
     }
+}
 
-    fn display_map(&self) {
-        let mut map: String = String::from(
-            "
-        [{ss}]
-        | |
-[{a}]==[{dal}--{dac}--{dar}]==[{k}]
-| |     | |     | |
-[{rr}-------{rr}]     | |
-| |             | |
-[{hl}]==[{sosl}--{soa}--{sosr}]==[{hr}]
-",
-        );
+// line-mode frontend: one command per tick until the night is decided
+fn play_night_turns(night: &mut Night, input: &Input) -> TickOutcome {
+    loop {
+        println!("{}", night.status());
 
-        /*
-                      [{ss}]
-                      | |
-              [{a}]==[{dal}--{dac}--{dar}]==[{k}]
-              | |     | |     | |
-              [{rr}-------{rr}}]     | |
-              | |             | |
-              [{hl}]==[{sosl}--{soa}--{sosr}]==[{hr}]
-        "
-
-         */
-
-        for (_i, anim) in self.anim_states.iter().enumerate() {
-            match &anim.location {
-                Locations::ShowStage => {
-                    map = map.replace(
-                        "{ss}",
-                        format!("{}", {
-                            if anim.current_tell.value() == "v" {
-                                anim.name.chars().next().unwrap().to_string()
-                            } else {
-                                anim.current_tell.value().to_string()
-                            }
-                        })
-                        .as_str(),
-                    );
-                }
-                Locations::Arcade => {
-                    map = map.replace(
-                        "{a}",
-                        format!("{}", {
-                            if anim.current_tell.value() == "v" {
-                                anim.name.chars().next().unwrap().to_string()
-                            } else {
-                                anim.current_tell.value().to_string()
-                            }
-                        })
-                        .as_str(),
-                    );
-                }
-                Locations::DiningAreaL => {
-                    map = map.replace(
-                        "{dal}",
-                        format!("{}", {
-                            if anim.current_tell.value() == "v" {
-                                anim.name.chars().next().unwrap().to_string()
-                            } else {
-                                anim.current_tell.value().to_string()
-                            }
-                        })
-                        .as_str(),
-                    );
-                }
-                Locations::DiningAreaC => {
-                    map = map.replace(
-                        "{dac}",
-                        format!("{}", {
-                            if anim.current_tell.value() == "v" {
-                                anim.name.chars().next().unwrap().to_string()
-                            } else {
-                                anim.current_tell.value().to_string()
-                            }
-                        })
-                        .as_str(),
-                    );
-                }
-                Locations::DiningAreaR => {
-                    map = map.replace(
-                        "{dar}",
-                        format!("{}", {
-                            if anim.current_tell.value() == "v" {
-                                anim.name.chars().next().unwrap().to_string()
-                            } else {
-                                anim.current_tell.value().to_string()
-                            }
-                        })
-                        .as_str(),
-                    );
-                }
-                Locations::Kitchen => {
-                    map = map.replace(
-                        "{k}",
-                        format!("{}", {
-                            if anim.current_tell.value() == "v" {
-                                anim.name.chars().next().unwrap().to_string()
-                            } else {
-                                anim.current_tell.value().to_string()
-                            }
-                        })
-                        .as_str(),
-                    );
-                }
-                Locations::Restrooms => {
-                    map = map.replace(
-                        "{rr}",
-                        format!("{}", {
-                            if anim.current_tell.value() == "v" {
-                                anim.name.chars().next().unwrap().to_string()
-                            } else {
-                                anim.current_tell.value().to_string()
-                            }
-                        })
-                        .as_str(),
-                    );
-                }
-                Locations::HallwayL => {
-                    map = map.replace(
-                        "{hl}",
-                        format!("{}", {
-                            if anim.current_tell.value() == "v" {
-                                anim.name.chars().next().unwrap().to_string()
-                            } else {
-                                anim.current_tell.value().to_string()
-                            }
-                        })
-                        .as_str(),
-                    );
-                }
-                Locations::HallwayR => {
-                    map = map.replace(
-                        "{hr}",
-                        format!("{}", {
-                            if anim.current_tell.value() == "v" {
-                                anim.name.chars().next().unwrap().to_string()
-                            } else {
-                                anim.current_tell.value().to_string()
-                            }
-                        })
-                        .as_str(),
-                    );
-                }
-                Locations::SecurityOfficeStaticL => {
-                    map = map.replace(
-                        "{sosl}",
-                        format!("{}", {
-                            if anim.current_tell.value() == "v" {
-                                anim.name.chars().next().unwrap().to_string()
-                            } else {
-                                anim.current_tell.value().to_string()
-                            }
-                        })
-                        .as_str(),
-                    );
-                }
-                Locations::SecurityOfficeAttack => {
-                    map = map.replace(
-                        "{soa}",
-                        format!("{}", { anim.name.chars().next().unwrap().to_string() }).as_str(),
-                    );
-                }
-                Locations::SecurityOfficeStaticR => {
-                    map = map.replace(
-                        "{sosr}",
-                        format!("{}", {
-                            if anim.current_tell.value() == "v" {
-                                anim.name.chars().next().unwrap().to_string()
-                            } else {
-                                anim.current_tell.value().to_string()
-                            }
-                        })
-                        .as_str(),
-                    );
-                }
+        loop {
+            println!("{}", input.prompt());
+            let turn_input = input.read_move();
+
+            let accepted = night.enter(&turn_input);
+            print_messages(night);
+            if accepted {
+                break;
             }
         }
 
-        map = map.replace("{ss}", " ");
-        map = map.replace("{a}", " ");
-        map = map.replace("{dal}", " ");
-        map = map.replace("{dac}", " ");
-        map = map.replace("{dar}", " ");
-        map = map.replace("{k}", " ");
-        map = map.replace("{rr}", " ");
-        map = map.replace("{hl}", " ");
-        map = map.replace("{hr}", " ");
-        map = map.replace("{sosl}", " ");
-        map = map.replace("{soa}", " ");
-        map = map.replace("{sosr}", " ");
-
-        println!("{map}");
+        let outcome = night.tick();
+        print_messages(night);
+
+        if night_over(night, &outcome) {
+            return outcome;
+        }
     }
 }
 
-fn main() {
-    const START_TIME: u32 = 0;
-    const END_TIME: u32 = 6 * 60;
-    const TICK_RATE: u32 = 15; // 15 minutes at a time
+// real-time frontend: the night ticks every `tick_length` whether anything
+// was typed or not, commands take effect the moment they are entered
+fn play_night_realtime(night: &mut Night, input: &Input, tick_length: Duration) -> TickOutcome {
+    println!("{}", night.status());
+    let mut next_tick = Instant::now() + tick_length;
 
-    let mut time: u32 = START_TIME;
+    loop {
+        while let Some(line) = input.read_move_until(next_tick) {
+            night.enter(&line);
+            print_messages(night);
+        }
+        next_tick += tick_length;
 
-    let mut night: u8 = 1;
+        let outcome = night.tick();
+        print_messages(night);
 
-    let mut freddy_state_power_down: u8 = 0;
+        if night_over(night, &outcome) {
+            return outcome;
+        }
+        println!("{}", night.status());
+    }
+}
 
-    let mut battery = Battery::new();
+fn main() {
+    let options = parse_args();
+
+    let layout = match &options.map {
+        Some(path) => Layout::load(path).unwrap_or_else(|err| {
+            eprintln!("Could not load map {path}: {err}");
+            std::process::exit(1);
+        }),
+        None => Layout::default(),
+    };
+
+    let roster = match &options.roster {
+        Some(path) => Roster::load(path, &layout),
+        None => Roster::default_for(&layout),
+    }
+    .unwrap_or_else(|err| {
+        eprintln!("Could not load the roster: {err}");
+        std::process::exit(1);
+    });
+
+    let campaign = match &options.campaign {
+        Some(path) => Campaign::load(path, &roster).unwrap_or_else(|err| {
+            eprintln!("Could not load campaign {path}: {err}");
+            std::process::exit(1);
+        }),
+        None => Campaign::default_for(&roster),
+    };
+
+    let extras = match &options.extras {
+        Some(path) => Extras::load(path, &roster).unwrap_or_else(|err| {
+            eprintln!("Could not load extras {path}: {err}");
+            std::process::exit(1);
+        }),
+        None => Extras::default_for(&roster),
+    };
+
+    if options.simulate {
+        run_simulation(&layout, &roster, &options);
+        return;
+    }
 
-    let mut map = Map::new();
+    if let Some(path) = &options.replay {
+        let all_match = run_replay(path, &layout, &roster);
+        std::process::exit(if all_match { 0 } else { 1 });
+    }
 
+    let mut recorder = options.record.clone().map(|path| Recorder {
+        path,
+        replay: Replay::new(),
+    });
+    let keymap = match &options.keymap {
+        Some(path) => Keymap::load(path).unwrap_or_else(|err| {
+            eprintln!("Could not load keymap {path}: {err}");
+            std::process::exit(1);
+        }),
+        None => Keymap::default(),
+    };
+
+    // typed commands stay around for scripts piping into stdin
+    let keys = !options.typed && (options.tui || std::io::stdin().is_terminal());
+    let input = Input::spawn(keys.then_some(keymap.clone()));
     let mut turn_input: String = String::new();
 
+    let saves = match &options.save_dir {
+        Some(dir) => Some(Saves::new(dir.into())),
+        None => Saves::default_dir().map(Saves::new),
+    };
+    if saves.is_none() {
+        println!("There is nowhere to keep saves, progress only lasts until you exit.");
+    }
+    let mut progress = Progress::load(saves, options.slot.clone());
+
     println!("Welcome to Five Nights at Freddy's. ");
     loop {
-        loop {
-            println!("Main Menu: ");
-            println!(
-                "Please select an option.\n\t New Game \n\t Custom Night \n\t Explain \n\t Exit"
-            );
-            turn_input.clear();
-            let _ = std::io::stdin().read_line(&mut turn_input).unwrap();
-
-            match turn_input.trim().to_lowercase().as_str() {
-                "new game" => {
-                    night = 1;
-                    let mut animatronics = Vec::new();
-
-                    let freddy = Animatronic::new("Freddy".to_string(), Locations::ShowStage, 5);
-                    let bonnie = Animatronic::new("Bonnie".to_string(), Locations::ShowStage, 3);
-                    let chica = Animatronic::new("Chica".to_string(), Locations::ShowStage, 3);
-                    animatronics.push(freddy);
-                    animatronics.push(bonnie);
-                    animatronics.push(chica);
-
-                    map.anim_states = animatronics;
-
-                    // five night cycle.
-                    loop {
-                        println!("Dusk of Night {night}", night = night);
-                        time = START_TIME;
-                        battery = Battery::new();
-                        freddy_state_power_down = 0;
-                        map.night_reset();
-
-                        // night loop.
-                        loop {
-                            let (hours, minutes) = display_time(time);
-
-                            turn_input.clear();
-
-                            println!(
-                                "Time: {:02}:{:02}\nBattery: {}%",
-                                hours, minutes, battery.power
-                            );
-
-                            println!("Office State: \n\tLeft Door: {}\n\tRight Door: {}\n\tLeft Light: {}\n\tRight Light: {}",
-                            if map.left_door_closed { "Closed" } else { "Open" },
-                            if map.right_door_closed { "Closed" } else { "Open" },
-                            if map.left_light_on { "On" } else { "Off" },
-                            if map.right_light_on { "On" } else { "Off" });
-
-                            loop {
-                                println!("What is your move this turn? : ");
-
-                                let _ = std::io::stdin().read_line(&mut turn_input).unwrap();
-
-                                if battery.is_online {
-                                    match turn_input.trim() {
-                                        "left door" => {
-                                            map.left_door_closed = !map.left_door_closed;
-                                            if map.left_door_closed {
-                                                battery.add_power_draw(PowerDraw::Doors);
-                                            } else {
-                                                battery.remove_power_draw(PowerDraw::Doors);
-                                            }
-                                            break;
-                                        }
-                                        "left light" => {
-                                            map.left_light_on = !map.left_light_on;
-                                            if map.left_light_on {
-                                                battery.add_power_draw(PowerDraw::Lights);
-                                            } else {
-                                                battery.remove_power_draw(PowerDraw::Lights);
-                                            }
-                                            break;
-                                        }
-                                        "right door" => {
-                                            map.right_door_closed = !map.right_door_closed;
-                                            if map.right_door_closed {
-                                                battery.add_power_draw(PowerDraw::Doors);
-                                            } else {
-                                                battery.remove_power_draw(PowerDraw::Doors);
-                                            }
-                                            break;
-                                        }
-                                        "right light" => {
-                                            map.right_light_on = !map.right_light_on;
-                                            if map.right_light_on {
-                                                battery.add_power_draw(PowerDraw::Lights);
-                                            } else {
-                                                battery.remove_power_draw(PowerDraw::Lights);
-                                            }
-                                            break;
-                                        }
-                                        "camera" => {
-                                            battery.add_power_draw(PowerDraw::Camera);
-                                            map.display_map();
-                                            break;
-                                        }
-                                        "sit" => {
-                                            break;
-                                        }
-                                        _ => {
-                                            println!("Invalid command!");
-                                            turn_input.clear();
-                                        }
-                                    }
-                                } else {
-                                    match turn_input.trim() {
-                                        "sit" => {
-                                            break;
-                                        }
-                                        _ => {
-                                            println!("Invalid command!");
-                                            turn_input.clear();
-                                        }
-                                    }
-                                }
-                            }
-
-                            battery.update_power();
-                            battery.remove_power_draw(PowerDraw::Camera);
-
-                            if battery.power == 0 {
-                                println!("You ran out of power! All systems are down!");
-                                battery.shutdown();
-                                map.left_door_closed = false;
-                                map.right_door_closed = false;
-                                map.left_light_on = false;
-                                map.right_light_on = false;
-                                map.anim_states[0].location = Locations::HallwayR;
-                            }
-
-                            if battery.is_online == false
-                                && map.anim_states[0].location == Locations::HallwayR
-                            {
-                                match freddy_state_power_down {
-                                    0 => {
-                                        println!("You see glowing eyes to your right.");
-                                    }
-                                    1 => {
-                                        println!("You hear a voice say, 'It's me.'");
-                                    }
-                                    2 => {
-                                        println!("You hear a voice say, 'I am still here.'");
-                                    }
-                                    3 => {
-                                        println!("You hear a voice say, 'I am always here.'");
-                                    }
-                                    4 => {
-                                        println!("You hear a voice say, 'I am always watching.'");
-                                    }
-                                    5 => {
-                                        println!(
-                                            "You hear a voice say, 'I am always watching you.'"
-                                        );
-                                    }
-                                    _ => {
-                                        println!("There is silence.");
-                                        map.anim_states[0].location =
-                                            Locations::SecurityOfficeAttack;
-                                        map.killer = String::from("Freddy");
-                                        map.is_dead = true;
-                                    }
-                                }
-                                freddy_state_power_down += 1;
-                            }
-
-                            if map.is_dead {
-                                println!(
-                                    "You were attacked by {name}! Game over!",
-                                    name = map.killer
-                                );
-                                break;
-                            }
-
-                            time += TICK_RATE;
-
-                            if time >= END_TIME {
-                                println!("You survived the night! Congratulations! \n");
-                                night += 1;
-                                break;
-                            }
-
-                            map.map_tick();
-                        }
-                        if map.is_dead {
-                            break;
-                        }
-                        if night > 5 {
-                            println!("You survived all 5 nights! Congratulations! \n");
-                            night = 1;
-                            break;
-                        }
-                        for anim in map.anim_states.iter_mut() {
-                            let mut rng = rand::thread_rng();
-                            anim.difficulty += rng.gen_range(1..3);
-                        }
-                    }
-                    break;
+        println!("Main Menu (slot {}): ", progress.slot);
+        print_stars(&progress.save);
+        let mut options_text = String::new();
+        if progress.save.in_progress() {
+            options_text += &format!("\n\t Continue (Night {}) ", progress.save.night);
+        }
+        options_text += "\n\t New Game ";
+        if progress.save.night_six {
+            options_text += "\n\t Night 6 ";
+        }
+        options_text += "\n\t Custom Night \n\t Slots \n\t Explain \n\t Exit";
+        println!("Please select an option.{options_text}");
+        input.read_line(&mut turn_input);
+
+        match turn_input.trim().to_lowercase().as_str() {
+            "continue" if progress.save.in_progress() => {
+                play_campaign(
+                    &mut progress,
+                    &campaign,
+                    &layout,
+                    &roster,
+                    &input,
+                    &options,
+                    &mut recorder,
+                );
+            }
+            "new game" if options.night.is_some_and(|night| night > campaign.len()) => {
+                println!("The campaign only has {} nights.\n", campaign.len());
+            }
+            "new game" if options.night.is_some() => {
+                // --night skips ahead, so nothing it plays goes into the save
+                let night = options.night.unwrap_or(1);
+                let mut practice = Progress {
+                    saves: None,
+                    slot: progress.slot.clone(),
+                    save: Save::default(),
+                };
+                practice
+                    .save
+                    .start_night(night, options.seed.unwrap_or_else(rand::random));
+                println!("Practice from Night {night}, progress is not saved.\n");
+
+                play_campaign(
+                    &mut practice,
+                    &campaign,
+                    &layout,
+                    &roster,
+                    &input,
+                    &options,
+                    &mut recorder,
+                );
+            }
+            "new game" => {
+                // stars and unlocks carry over, only the campaign starts again
+                let seed = options.seed.unwrap_or_else(rand::random);
+                progress.save.start_night(1, seed);
+                progress.store();
+
+                play_campaign(
+                    &mut progress,
+                    &campaign,
+                    &layout,
+                    &roster,
+                    &input,
+                    &options,
+                    &mut recorder,
+                );
+            }
+            "night 6" | "night six" if !progress.save.night_six => {
+                println!("Survive all {} nights to unlock Night 6.\n", campaign.len());
+            }
+            "night 6" | "night six" => {
+                let (_, outcome) = play_single_night(
+                    "Night 6",
+                    animatronics_at(&extras.night_6, &roster),
+                    campaign.default_ramp(),
+                    &layout,
+                    &input,
+                    &options,
+                    &mut recorder,
+                );
+                if outcome == TickOutcome::Survived {
+                    progress.save.complete_night_six();
+                    progress.store();
                 }
-                "custom night" => {
-                    turn_input.clear();
-
-                    let mut animatronics = Vec::new();
-                    println!("Please enter the difficulty for Freddy: ");
-                    let _ = std::io::stdin().read_line(&mut turn_input).unwrap();
-                    let freddy_difficulty: u8 = match turn_input.trim().parse::<u8>() {
-                        Ok(val) => val,
-                        Err(_) => 5,
-                    };
-                    turn_input.clear();
-
-                    // println!("freedddy diff: {}", freddy_difficulty);
-
-                    println!("Please enter the difficulty for Bonnie: ");
-                    let _ = std::io::stdin().read_line(&mut turn_input).unwrap();
-                    let bonnie_difficulty: u8 = match turn_input.trim().parse::<u8>() {
-                        Ok(val) => val,
-                        Err(_) => 3,
-                    };
-                    turn_input.clear();
-                    // println!("bonniee diff: {}", bonnie_difficulty);
-
-                    println!("Please enter the difficulty for Chica: ");
-                    let _ = std::io::stdin().read_line(&mut turn_input).unwrap();
-                    let chica_difficulty: u8 = match turn_input.trim().parse::<u8>() {
-                        Ok(val) => val,
-                        Err(_) => 3,
-                    };
-                    turn_input.clear();
-                    // println!("chica diff: {}", chica_difficulty);
-
-                    let freddy = Animatronic::new(
-                        "Freddy".to_string(),
-                        Locations::ShowStage,
-                        freddy_difficulty,
-                    );
-                    let bonnie = Animatronic::new(
-                        "Bonnie".to_string(),
-                        Locations::ShowStage,
-                        bonnie_difficulty,
-                    );
-                    let chica = Animatronic::new(
-                        "Chica".to_string(),
-                        Locations::ShowStage,
-                        chica_difficulty,
-                    );
-                    animatronics.push(freddy);
-                    animatronics.push(bonnie);
-                    animatronics.push(chica);
-
-                    map.anim_states = animatronics;
-
-                    println!("Dusk of Custom Night");
-                    turn_input.clear();
-
-                    time = START_TIME;
-                    battery = Battery::new();
-                    freddy_state_power_down = 0;
-                    map.night_reset();
-
-                    loop {
-                        let (hours, minutes) = display_time(time);
-
-                        turn_input.clear();
-
-                        println!(
-                            "Time: {:02}:{:02}\nBattery: {}%",
-                            hours, minutes, battery.power
-                        );
-
-                        println!("Office State: \n\tLeft Door: {}\n\tRight Door: {}\n\tLeft Light: {}\n\tRight Light: {}",
-                        if map.left_door_closed { "Closed" } else { "Open" },
-                        if map.right_door_closed { "Closed" } else { "Open" },
-                        if map.left_light_on { "On" } else { "Off" },
-                        if map.right_light_on { "On" } else { "Off" });
-
-                        loop {
-                            println!("What is your move this turn? : ");
-
-                            let _ = std::io::stdin().read_line(&mut turn_input).unwrap();
-
-                            if battery.is_online {
-                                match turn_input.trim() {
-                                    "left door" => {
-                                        map.left_door_closed = !map.left_door_closed;
-                                        if map.left_door_closed {
-                                            battery.add_power_draw(PowerDraw::Doors);
-                                        } else {
-                                            battery.remove_power_draw(PowerDraw::Doors);
-                                        }
-                                        break;
-                                    }
-                                    "left light" => {
-                                        map.left_light_on = !map.left_light_on;
-                                        if map.left_light_on {
-                                            battery.add_power_draw(PowerDraw::Lights);
-                                        } else {
-                                            battery.remove_power_draw(PowerDraw::Lights);
-                                        }
-                                        break;
-                                    }
-                                    "right door" => {
-                                        map.right_door_closed = !map.right_door_closed;
-                                        if map.right_door_closed {
-                                            battery.add_power_draw(PowerDraw::Doors);
-                                        } else {
-                                            battery.remove_power_draw(PowerDraw::Doors);
-                                        }
-                                        break;
-                                    }
-                                    "right light" => {
-                                        map.right_light_on = !map.right_light_on;
-                                        if map.right_light_on {
-                                            battery.add_power_draw(PowerDraw::Lights);
-                                        } else {
-                                            battery.remove_power_draw(PowerDraw::Lights);
-                                        }
-                                        break;
-                                    }
-                                    "camera" => {
-                                        battery.add_power_draw(PowerDraw::Camera);
-                                        map.display_map();
-                                        break;
-                                    }
-                                    "sit" => {
-                                        break;
-                                    }
-                                    _ => {
-                                        println!("Invalid command!");
-                                        turn_input.clear();
-                                    }
-                                }
-                            } else {
-                                match turn_input.trim() {
-                                    "sit" => {
-                                        break;
-                                    }
-                                    _ => {
-                                        println!("Invalid command!");
-                                        turn_input.clear();
-                                    }
-                                }
-                            }
-                        }
-
-                        battery.update_power();
-                        battery.remove_power_draw(PowerDraw::Camera);
-
-                        if battery.power == 0 {
-                            println!("You ran out of power! All systems are down!");
-                            battery.shutdown();
-                            map.left_door_closed = false;
-                            map.right_door_closed = false;
-                            map.left_light_on = false;
-                            map.right_light_on = false;
-                            map.anim_states[0].location = Locations::HallwayR;
-                        }
-
-                        if battery.is_online == false
-                            && map.anim_states[0].location == Locations::HallwayR
-                        {
-                            match freddy_state_power_down {
-                                0 => {
-                                    println!("You see glowing eyes to your right.");
-                                }
-                                1 => {
-                                    println!("You hear a voice say, 'It's me.'");
-                                }
-                                2 => {
-                                    println!("You hear a voice say, 'I am still here.'");
-                                }
-                                3 => {
-                                    println!("You hear a voice say, 'I am always here.'");
-                                }
-                                4 => {
-                                    println!("You hear a voice say, 'I am always watching.'");
-                                }
-                                5 => {
-                                    println!("You hear a voice say, 'I am always watching you.'");
-                                }
-                                _ => {
-                                    println!("There is silence.");
-                                    map.anim_states[0].location = Locations::SecurityOfficeAttack;
-                                    map.killer = String::from("Freddy");
-                                    map.is_dead = true;
-                                }
-                            }
-                            freddy_state_power_down += 1;
-                        }
-
-                        if map.is_dead {
-                            println!("You were attacked by {name}! Game over!", name = map.killer);
-                            break;
-                        }
-
-                        time += TICK_RATE;
-
-                        if time >= END_TIME {
-                            println!("You survived the night! Congratulations!");
-                            night += 1;
-                            break;
-                        }
-
-                        map.map_tick();
-                    }
-
-                    break;
+            }
+            "custom night" if !progress.save.custom_night => {
+                println!(
+                    "Survive all {} nights to unlock Custom Night.\n",
+                    campaign.len()
+                );
+            }
+            "custom night" => {
+                let Some(animatronics) = pick_custom_night(&roster, &extras, &input) else {
+                    continue;
+                };
+
+                // no ramp, the levels stay the way they were picked
+                let (night, outcome) = play_single_night(
+                    "Custom Night",
+                    animatronics,
+                    Vec::new(),
+                    &layout,
+                    &input,
+                    &options,
+                    &mut recorder,
+                );
+                if outcome == TickOutcome::Survived {
+                    progress.save.complete_custom_night(&night.start_levels());
+                    progress.store();
                 }
-                "explain" => {
-                    println!("Five Nights at Freddy's is a survival horror game where you play as a security guard at Freddy Fazbear's Pizza. \n You must survive the night by managing your power and keeping the animatronics at bay. \n The animatronics will move around the pizzeria and try to attack you. You must use the cameras and doors to keep them away. \n If you run out of power, you will be attacked and the game will be over. Good luck!\n");
-
-                    println!("Commands: \n\t left door -- open/close left door \n\t right door -- open/close right door \n\t left light -- turn on/off left light \n\t right light -- turn on/off right light \n\t camera -- check cameras \n\t sit -- do nothing \n");
-
-                    println!("Tells: \n\t l -- laughing \n\t n -- noise \n\t f -- footsteps \n\t s -- static \n\t v -- visual \n\t b -- breathing \n");
-
-                    println!("Locations: \n\t Show Stage \n\t Dining Area L \n\t Dining Area R \n\t Dining Area C \n\t Restrooms \n\t Kitchen \n\t Arcade \n\t Security Office Static R \n\t Security Office Static L \n\t Security Office Attack \n\t Hallway L \n\t Hallway R \n");
+            }
+            "slots" => manage_slots(&mut progress, &input),
+            "explain" => {
+                println!("Five Nights at Freddy's is a survival horror game where you play as a security guard at Freddy Fazbear's Pizza. \n You must survive the night by managing your power and keeping the animatronics at bay. \n The animatronics will move around the pizzeria and try to attack you. You must use the cameras and doors to keep them away. \n If you run out of power, you will be attacked and the game will be over. Good luck!\n");
 
-                    println!("Animatronics: \n\t F - Freddy \n\t B - Bonnie \n\t C - Chica \n");
+                println!("Commands (any case, several in one turn with ';'): \n\t left door, ld -- open/close left door \n\t right door, rd -- open/close right door \n\t close left, open right door -- only ever close or open \n\t left light, ll -- turn on/off left light \n\t right light, rl -- turn on/off right light \n\t left light on, right off -- only ever turn on or off \n\t camera, cam -- look at the last camera again \n\t cam 1A, 2A -- switch to another camera, costs a little power \n\t sit, wait -- do nothing \n");
 
-                    break;
+                println!("Keys (--typed to type commands instead): ");
+                for (key, command) in keymap.bindings.iter() {
+                    match key {
+                        ' ' => println!("\t space -- {command}"),
+                        _ => println!("\t {key} -- {command}"),
+                    }
                 }
-                "exit" => {
-                    return;
+                println!("\t enter -- type any command, like cam 2A");
+                println!();
+
+                println!("Tells: \n\t l -- laughing \n\t n -- noise \n\t f -- footsteps \n\t s -- static \n\t v -- visual \n\t b -- breathing \n\t g -- you are seeing things, check the camera again as soon as you can \n");
+
+                println!("Locations: \n\t Show Stage \n\t Dining Area L \n\t Dining Area R \n\t Dining Area C \n\t Restrooms \n\t Kitchen \n\t Arcade \n\t Security Office Static R \n\t Security Office Static L \n\t Security Office Attack \n\t Hallway L \n\t Hallway R \n\t Pirate Cove -- shows how far Foxy has come out from behind the curtain \n");
+
+                println!("Cameras: ");
+                let mut cameras: Vec<_> = layout
+                    .rooms
+                    .iter()
+                    .filter_map(|room| room.camera.map(|camera| (camera, room)))
+                    .collect();
+                cameras.sort_by_key(|(camera, _)| *camera);
+                for (camera, room) in cameras {
+                    let sound = if room.audio_only { " (audio only)" } else { "" };
+                    println!("\t {camera} -- {}{sound}", room.name());
                 }
-                _ => {
-                    println!("Invalid command!");
-                    turn_input.clear();
+                println!();
+
+                println!("Animatronics: ");
+                for profile in roster.characters.iter() {
+                    println!("\t {} - {} ", profile.glyph, profile.name);
                 }
+                println!();
+            }
+            "exit" => {
+                return;
+            }
+            _ => {
+                println!("Invalid command!");
             }
         }
     }
 }
-
-fn display_time(time: u32) -> (u32, u32) {
-    let hours = time / 60;
-    let minutes = time % 60;
-    (hours, minutes)
-}
@@ -0,0 +1,238 @@
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::animatronics::{Animatronic, Tells};
+use crate::behavior::AiMode;
+use crate::commands::Side;
+use crate::layout::{Door, Layout, Room};
+
+// every room a map file can use, a map picks from these and can't add
+// its own
+#[derive(PartialEq, Hash, Clone, Copy, Eq, Debug, Deserialize)]
+pub enum Locations {
+    ShowStage,
+    DiningAreaL,
+    DiningAreaR,
+    DiningAreaC,
+    Restrooms,
+    Kitchen,
+    Arcade,
+    SecurityOfficeStaticR,
+    SecurityOfficeStaticL,
+    SecurityOfficeAttack,
+    HallwayL,
+    HallwayR,
+    PirateCove,
+}
+
+pub struct Map {
+    pub layout: Layout,
+    pub left_door_closed: bool,
+    pub right_door_closed: bool,
+    pub left_light_on: bool,
+    pub right_light_on: bool,
+    pub anim_states: Vec<Animatronic>,
+    pub is_dead: bool,
+    pub killer: String,
+    // set by the night whenever the camera was up this turn
+    pub camera_used: bool,
+    // power knocked off by banging on doors during the last map_tick
+    pub power_penalty: u8,
+    // drawn over the room on the next render, see golden_freddy
+    pub hallucination: Option<Locations>,
+    // the room on the monitor, switching away from it costs power
+    pub camera: Option<Locations>,
+    pub ai: AiMode,
+}
+
+impl Default for Map {
+    fn default() -> Map {
+        Map::new()
+    }
+}
+
+impl Map {
+    pub fn new() -> Map {
+        Map::with_layout(Layout::default())
+    }
+
+    pub fn with_layout(layout: Layout) -> Map {
+        Map {
+            layout,
+            left_door_closed: false,
+            right_door_closed: false,
+            left_light_on: false,
+            right_light_on: false,
+            anim_states: Vec::new(),
+            is_dead: false,
+            killer: String::from("MissingNo."),
+            camera_used: false,
+            power_penalty: 0,
+            hallucination: None,
+            camera: None,
+            ai: AiMode::Classic,
+        }
+    }
+
+    // default behavior for everyone who doesn't have one in the roster
+    pub fn set_ai(&mut self, ai: AiMode) {
+        self.ai = ai;
+        for anim in self.anim_states.iter_mut() {
+            anim.set_ai(ai);
+        }
+    }
+
+    pub fn night_reset(&mut self) {
+        // reset the map
+        self.left_door_closed = false;
+        self.right_door_closed = false;
+        self.left_light_on = false;
+        self.right_light_on = false;
+        self.is_dead = false;
+        self.killer = String::from("MissingNo.");
+        self.camera_used = false;
+        self.power_penalty = 0;
+        self.camera = self.layout.first_camera();
+
+        for anim in self.anim_states.iter_mut() {
+            anim.location = anim.profile.start;
+            anim.current_tell = Tells::Visual;
+            anim.stage = 0;
+            anim.sprinting = false;
+            anim.waited = 0;
+        }
+    }
+
+    // `minutes` of in-game time pass, everyone moves as often as their
+    // own rhythm lets them in that time
+    pub fn map_tick(&mut self, minutes: u32, rng: &mut impl Rng) -> Vec<String> {
+        let mut sightings = Vec::new();
+
+        let closed_doors = self.closed_doors();
+
+        for anim in self.anim_states.iter_mut() {
+            for _ in 0..anim.opportunities(minutes) {
+                let banged = anim.move_tick(&self.layout, &closed_doors, self.camera_used, rng);
+
+                if let (Some(door), Some(cove)) = (banged, &anim.profile.cove) {
+                    sightings.push(format!("You hear banging on the {} door!", door.side));
+                    self.power_penalty = self.power_penalty.saturating_add(cove.bang_power);
+                }
+
+                if anim.location == self.layout.attack_room {
+                    self.is_dead = true;
+                    self.killer = anim.name.clone();
+                    break;
+                }
+            }
+
+            for door in self.layout.doors.iter() {
+                let light_on = match door.side {
+                    Side::Left => self.left_light_on,
+                    Side::Right => self.right_light_on,
+                };
+
+                // right outside the door, close enough to hear
+                if anim.location == door.room {
+                    sightings.push(format!("You hear breathing at the {} door.", door.side));
+                }
+
+                if anim.location == door.hallway && light_on {
+                    sightings.push(format!(
+                        "You see {} is at the {} door!",
+                        anim.name, door.side
+                    ));
+                }
+            }
+        }
+
+        self.camera_used = false;
+
+        sightings
+    }
+
+    pub fn closed_doors(&self) -> Vec<Door> {
+        self.layout
+            .doors
+            .iter()
+            .filter(|door| match door.side {
+                Side::Left => self.left_door_closed,
+                Side::Right => self.right_door_closed,
+            })
+            .copied()
+            .collect()
+    }
+
+    // the whole building, everyone in it
+    pub fn render_map(&self) -> String {
+        self.render(None)
+    }
+
+    // what one camera sees: the map with only its room filled in
+    pub fn render_camera(&self, location: Locations) -> String {
+        let Some(room) = self.layout.room(location) else {
+            return String::new();
+        };
+
+        let camera = match room.camera {
+            Some(camera) => format!("CAM {camera}"),
+            None => String::from("CAM"),
+        };
+        let mut view = format!("{camera} - {}", room.name());
+        if room.audio_only {
+            view.push_str(" (audio only)");
+        }
+        view.push_str(&self.render(Some(room)));
+        view
+    }
+
+    fn render(&self, only: Option<&Room>) -> String {
+        let mut map = self.layout.template.clone();
+        let audio_only = only.is_some_and(|room| room.audio_only);
+
+        for room in self.layout.rooms.iter() {
+            let shown = only.is_none_or(|only| only.id == room.id);
+            let mut glyphs = String::new();
+
+            if shown && !audio_only && self.hallucination == Some(room.id) {
+                glyphs.push_str(Tells::Hallucination.value());
+            }
+
+            // everyone in the room, in roster order so the same night
+            // always draws the same way
+            for anim in self.anim_states.iter() {
+                if !shown || anim.location != room.id {
+                    continue;
+                }
+                // nothing to see on an audio only camera, only to hear
+                if audio_only && !anim.current_tell.is_audio() {
+                    continue;
+                }
+                glyphs.push_str(&self.glyph(anim));
+            }
+
+            if glyphs.is_empty() {
+                glyphs.push(' ');
+            }
+            map = map.replace(&format!("{{{}}}", room.label), &glyphs);
+        }
+
+        map
+    }
+
+    fn glyph(&self, anim: &Animatronic) -> String {
+        if anim.location == self.layout.attack_room {
+            return anim.profile.glyph.to_string();
+        }
+
+        // the cove shows how far the curtain has been pulled back
+        if anim.profile.cove.is_some() && anim.location == anim.profile.start {
+            return anim.stage.to_string();
+        }
+
+        match anim.current_tell {
+            Tells::Visual => anim.profile.glyph.to_string(),
+            tell => tell.value().to_string(),
+        }
+    }
+}
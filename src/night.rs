@@ -0,0 +1,409 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::animatronics::{Animatronic, MAX_DIFFICULTY};
+use crate::blackout::{Blackout, PowerOut};
+use crate::campaign::RampStep;
+use crate::clock::{Clock, TICK_RATE};
+use crate::commands::{Command, Side};
+use crate::golden_freddy::{self, GoldenFreddy, Hallucination};
+use crate::layout::Layout;
+use crate::map::{Locations, Map};
+use crate::power::{self, Battery, PowerDraw};
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum TickOutcome {
+    Continue,
+    Survived,
+    Killed(String),
+}
+
+// everything that happens between dusk and 6 AM lives here, the frontend
+// just feeds it commands and shows whatever ends up in `messages` and the
+// camera view
+pub struct Night {
+    pub map: Map,
+    pub battery: Battery,
+    pub clock: Clock,
+    pub blackout: Blackout,
+    pub golden_freddy: GoldenFreddy,
+    // every random decision of the night comes out of this, so a seed
+    // and the same inputs always play out the same way
+    pub seed: u64,
+    pub rng: StdRng,
+    // ticks elapsed since dusk and every accepted command with the tick
+    // it was entered on, which is all a replay needs
+    pub ticks: u32,
+    pub inputs: Vec<(u32, Command)>,
+    // AI levels gained on the hour, every night starts back at `levels`
+    pub ramp: Vec<RampStep>,
+    levels: Vec<u8>,
+    // says every AI level change in the messages
+    debug: bool,
+    messages: Vec<String>,
+    // what the camera showed since the frontend last looked
+    camera_views: Vec<String>,
+}
+
+impl Night {
+    pub fn new(layout: Layout, animatronics: Vec<Animatronic>, seed: u64) -> Night {
+        let golden_freddy = GoldenFreddy::new(layout.hallucination_room);
+        let levels = animatronics.iter().map(|anim| anim.difficulty).collect();
+        let mut map = Map::with_layout(layout);
+        map.anim_states = animatronics;
+
+        let mut night = Night {
+            map,
+            battery: Battery::new(),
+            clock: Clock::new(),
+            blackout: Blackout::new(),
+            golden_freddy,
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+            ticks: 0,
+            inputs: Vec::new(),
+            ramp: Vec::new(),
+            levels,
+            debug: false,
+            messages: Vec::new(),
+            camera_views: Vec::new(),
+        };
+        night.reset(seed);
+        night
+    }
+
+    pub fn reset(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = StdRng::seed_from_u64(seed);
+        self.clock = Clock::new();
+        self.battery = Battery::new();
+        self.blackout = Blackout::new();
+        self.ticks = 0;
+        self.inputs.clear();
+        self.messages.clear();
+        self.camera_views.clear();
+        self.map.night_reset();
+        for (anim, level) in self.map.anim_states.iter_mut().zip(self.levels.iter()) {
+            anim.difficulty = *level;
+        }
+        self.golden_freddy.roll(&mut self.rng);
+    }
+
+    pub fn status(&self) -> String {
+        format!(
+            "Time: {}\nBattery: {}% (usage {}/{})\nOffice State: \n\tLeft Door: {}\n\tRight Door: {}\n\tLeft Light: {}\n\tRight Light: {}",
+            self.clock,
+            self.battery.percent(),
+            self.battery.usage(),
+            power::MAX_USAGE,
+            if self.map.left_door_closed { "Closed" } else { "Open" },
+            if self.map.right_door_closed { "Closed" } else { "Open" },
+            if self.map.left_light_on { "On" } else { "Off" },
+            if self.map.right_light_on { "On" } else { "Off" }
+        )
+    }
+
+    // returns false if the command can't be used right now, the reason
+    // ends up in the messages
+    pub fn apply(&mut self, command: Command) -> bool {
+        if !self.battery.is_online && command != Command::Sit {
+            self.messages
+                .push(format!("Nothing happens on '{command}', the power is out."));
+            return false;
+        }
+
+        let camera = match command {
+            Command::Camera => self.map.camera,
+            Command::View(id) => self.map.layout.camera_room(id).map(|room| room.id),
+            _ => None,
+        };
+        if matches!(command, Command::Camera | Command::View(_)) && camera.is_none() {
+            self.messages.push(match command {
+                Command::View(id) => format!("There is no camera {id}."),
+                _ => String::from("There are no cameras here."),
+            });
+            return false;
+        }
+
+        self.inputs.push((self.ticks, command));
+
+        match command {
+            Command::Door(side) => {
+                let closed = self.door_closed(side);
+                self.set_door(side, !closed);
+            }
+            Command::Close(side) => self.set_door(side, true),
+            Command::Open(side) => self.set_door(side, false),
+            Command::Light(side) => {
+                let on = self.light_on(side);
+                self.set_light(side, !on);
+            }
+            Command::LightOn(side) => self.set_light(side, true),
+            Command::LightOff(side) => self.set_light(side, false),
+            Command::Camera | Command::View(_) => {
+                if let Some(room) = camera {
+                    self.view_camera(room);
+                }
+            }
+            Command::Sit => {}
+        }
+
+        true
+    }
+
+    fn view_camera(&mut self, room: Locations) {
+        if self.map.camera != Some(room) {
+            self.battery.drain(self.map.layout.camera_switch_power);
+            self.map.camera = Some(room);
+        }
+
+        self.battery.add_power_draw(PowerDraw::Camera);
+        self.map.camera_used = true;
+
+        let message = self.golden_freddy.camera_viewed(&[room]);
+        if self.golden_freddy.state == Hallucination::Seen {
+            self.map.hallucination = self.golden_freddy.room;
+        }
+
+        self.camera_views.push(self.map.render_camera(room));
+        self.map.hallucination = None;
+        if let Some(message) = message {
+            self.messages.push(String::from(message));
+        }
+    }
+
+    // a typed turn, every action in it is applied in order. returns false
+    // if nothing was, the reason ends up in the messages
+    pub fn enter(&mut self, line: &str) -> bool {
+        let commands = match Command::parse_line(line) {
+            Ok(commands) => commands,
+            Err(err) => {
+                self.messages.push(err);
+                return false;
+            }
+        };
+
+        let mut accepted = false;
+        for command in commands {
+            accepted |= self.apply(command);
+        }
+
+        accepted
+    }
+
+    pub fn door_closed(&self, side: Side) -> bool {
+        match side {
+            Side::Left => self.map.left_door_closed,
+            Side::Right => self.map.right_door_closed,
+        }
+    }
+
+    pub fn light_on(&self, side: Side) -> bool {
+        match side {
+            Side::Left => self.map.left_light_on,
+            Side::Right => self.map.right_light_on,
+        }
+    }
+
+    fn set_door(&mut self, side: Side, closed: bool) {
+        if self.door_closed(side) == closed {
+            return;
+        }
+
+        match side {
+            Side::Left => self.map.left_door_closed = closed,
+            Side::Right => self.map.right_door_closed = closed,
+        }
+
+        if closed {
+            self.battery.add_power_draw(PowerDraw::Door(side));
+        } else {
+            self.battery.remove_power_draw(PowerDraw::Door(side));
+        }
+    }
+
+    fn set_light(&mut self, side: Side, on: bool) {
+        if self.light_on(side) == on {
+            return;
+        }
+
+        match side {
+            Side::Left => self.map.left_light_on = on,
+            Side::Right => self.map.right_light_on = on,
+        }
+
+        if on {
+            self.battery.add_power_draw(PowerDraw::Light(side));
+        } else {
+            self.battery.remove_power_draw(PowerDraw::Light(side));
+        }
+    }
+
+    pub fn tick(&mut self) -> TickOutcome {
+        self.ticks += 1;
+
+        self.battery.update_power(TICK_RATE);
+        self.battery.remove_power_draw(PowerDraw::Camera);
+
+        if self.battery.is_online && self.battery.is_empty() {
+            self.messages
+                .push(String::from("You ran out of power! All systems are down!"));
+            self.battery.shutdown();
+            self.map.left_door_closed = false;
+            self.map.right_door_closed = false;
+            self.map.left_light_on = false;
+            self.map.right_light_on = false;
+            self.blackout.start();
+        } else {
+            self.blackout_tick();
+        }
+
+        let (message, struck) = self.golden_freddy.tick();
+        if let Some(message) = message {
+            self.messages.push(String::from(message));
+        }
+        if struck {
+            self.map.is_dead = true;
+            self.map.killer = String::from(golden_freddy::NAME);
+        }
+
+        if self.map.is_dead {
+            return TickOutcome::Killed(self.map.killer.clone());
+        }
+
+        self.clock.advance();
+
+        if self.clock.is_dawn() {
+            return TickOutcome::Survived;
+        }
+
+        if self.clock.time.is_multiple_of(60) {
+            self.ramp_up(self.clock.time / 60);
+        }
+
+        // in a blackout only the power_out character is coming
+        if self.battery.is_online {
+            let sightings = self.map.map_tick(TICK_RATE, &mut self.rng);
+            self.messages.extend(sightings);
+        }
+
+        let penalty = std::mem::take(&mut self.map.power_penalty);
+        self.battery.drain(penalty);
+
+        TickOutcome::Continue
+    }
+
+    // everyone the ramp names for this hour gets their extra AI levels
+    fn ramp_up(&mut self, hour: u32) {
+        for step in self.ramp.iter().filter(|step| step.hour == hour) {
+            let Some(anim) = self
+                .map
+                .anim_states
+                .iter_mut()
+                .find(|anim| anim.name.eq_ignore_ascii_case(&step.name))
+            else {
+                continue;
+            };
+
+            let before = anim.difficulty;
+            anim.difficulty = before.saturating_add(step.gain).min(MAX_DIFFICULTY);
+            if self.debug {
+                self.messages.push(format!(
+                    "[debug] {}: {} AI {} -> {}",
+                    self.clock, anim.name, before, anim.difficulty
+                ));
+            }
+        }
+    }
+
+    // from now on every AI level change ends up in the messages, starting
+    // with where everyone is at and the ramp still to come
+    pub fn set_debug(&mut self, debug: bool) {
+        self.debug = debug;
+        if !debug {
+            return;
+        }
+
+        let levels: Vec<String> = self
+            .map
+            .anim_states
+            .iter()
+            .map(|anim| format!("{} {}", anim.name, anim.difficulty))
+            .collect();
+        self.messages.push(format!(
+            "[debug] {}: AI levels {}",
+            self.clock,
+            levels.join(", ")
+        ));
+
+        let ramp: Vec<String> = self.ramp.iter().map(|step| step.to_string()).collect();
+        self.messages.push(if ramp.is_empty() {
+            String::from("[debug] no ramp tonight")
+        } else {
+            format!("[debug] ramp {}", ramp.join(" "))
+        });
+    }
+
+    // the AI levels everyone started the night on
+    pub fn start_levels(&self) -> Vec<(String, u8)> {
+        self.map
+            .anim_states
+            .iter()
+            .zip(self.levels.iter())
+            .map(|(anim, level)| (anim.name.clone(), *level))
+            .collect()
+    }
+
+    // whoever has power_out in the roster comes to the left door, plays
+    // the music box and then comes in, without one it just stays dark
+    fn blackout_tick(&mut self) {
+        let Some(i) = self
+            .map
+            .anim_states
+            .iter()
+            .position(|anim| anim.profile.power_out)
+        else {
+            return;
+        };
+
+        let (message, attacked) = self.blackout.tick(&mut self.rng);
+        if let Some(message) = message {
+            self.messages.push(String::from(message));
+        }
+
+        let performer = &mut self.map.anim_states[i];
+        match self.blackout.state {
+            PowerOut::Eyes | PowerOut::Jingle(_) => {
+                if let Some(door) = self.map.layout.door(Side::Left) {
+                    performer.location = door.hallway;
+                }
+            }
+            _ => {}
+        }
+
+        if attacked {
+            performer.location = self.map.layout.attack_room;
+            self.map.killer = performer.name.clone();
+            self.map.is_dead = true;
+        }
+    }
+
+    pub fn death_message(&self) -> String {
+        if self.map.killer == golden_freddy::NAME {
+            return format!(
+                "{} was never really there, but he got you anyway! Game over!",
+                golden_freddy::NAME
+            );
+        }
+
+        format!("You were attacked by {}! Game over!", self.map.killer)
+    }
+
+    pub fn take_messages(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.messages)
+    }
+
+    pub fn take_camera_views(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.camera_views)
+    }
+}
@@ -0,0 +1,101 @@
+use std::cmp;
+
+use crate::commands::Side;
+
+// the battery counts in thousandths of a percent so slow drains don't get
+// rounded away
+pub const UNIT: u32 = 1000;
+pub const FULL: u32 = 100 * UNIT;
+// how much one usage bar drains per in-game minute, a tenth of a percent:
+// sitting through a whole night at one bar costs 36%
+pub const DRAIN_PER_BAR: u32 = UNIT / 10;
+pub const MAX_USAGE: usize = 4;
+
+// everything in the office that can be switched on, each door and light on
+// its own
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PowerDraw {
+    Camera,
+    Light(Side),
+    Door(Side),
+}
+
+pub struct Battery {
+    pub charge: u32,
+    pub power_draw: Vec<PowerDraw>,
+    pub is_online: bool,
+}
+
+impl Default for Battery {
+    fn default() -> Battery {
+        Battery::new()
+    }
+}
+
+impl Battery {
+    pub fn new() -> Battery {
+        Battery {
+            charge: FULL,
+            power_draw: Vec::new(),
+            is_online: true,
+        }
+    }
+
+    // what the office display shows, only 0 once it is really empty
+    pub fn percent(&self) -> u32 {
+        self.charge.div_ceil(UNIT)
+    }
+
+    pub fn add_power_draw(&mut self, power_draw: PowerDraw) {
+        if !self.is_online {
+            return;
+        }
+
+        if !self.power_draw.contains(&power_draw) {
+            self.power_draw.push(power_draw);
+        }
+    }
+
+    pub fn remove_power_draw(&mut self, power_draw: PowerDraw) {
+        if !self.is_online {
+            return;
+        }
+
+        self.power_draw.retain(|draw| *draw != power_draw);
+    }
+
+    // the usage meter: one bar for the office itself plus one per thing
+    // drawing power, nothing once the power is gone
+    pub fn usage(&self) -> usize {
+        if !self.is_online {
+            return 0;
+        }
+
+        cmp::min(1 + self.power_draw.len(), MAX_USAGE)
+    }
+
+    // drains for `minutes` of in-game time at the current usage
+    pub fn update_power(&mut self, minutes: u32) {
+        let drain = self.usage() as u32 * DRAIN_PER_BAR * minutes;
+        self.charge = self.charge.saturating_sub(drain);
+    }
+
+    // a flat loss in whole percent, like something banging on a door
+    pub fn drain(&mut self, percent: u8) {
+        if !self.is_online {
+            return;
+        }
+
+        self.charge = self.charge.saturating_sub(percent as u32 * UNIT);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.charge == 0
+    }
+
+    pub fn shutdown(&mut self) {
+        self.charge = 0;
+        self.power_draw.clear();
+        self.is_online = false;
+    }
+}
@@ -0,0 +1,310 @@
+use std::fmt;
+
+use crate::animatronics::Animatronic;
+use crate::behavior::AiMode;
+use crate::campaign::RampStep;
+use crate::clock::Clock;
+use crate::commands::Command;
+use crate::layout::Layout;
+use crate::night::{Night, TickOutcome};
+use crate::roster::{quoted, unquoted, Roster};
+
+/*
+ Replay files are plain text, one night after another:
+
+    fnafterminal-replay 1
+    version 0.1.0
+    night seed 1234 ai hunt difficulties Freddy=5 Bonnie=3 Chica=3 ramp 2:Bonnie+1
+    0 left door
+    1 camera
+    end killed by Bonnie at 02:15
+
+ every input line is "<tick> <command>", ticks count from dusk. the
+ difficulties are the ones at dusk, `ramp` is only there if the night had
+ one. names with a space are in quotes, "Toy Bonnie"=3.
+*/
+const REPLAY_HEADER: &str = "fnafterminal-replay 1";
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Ending {
+    Survived,
+    Killed { killer: String, time: Clock },
+}
+
+impl Ending {
+    pub fn from_outcome(outcome: &TickOutcome, clock: Clock) -> Option<Ending> {
+        match outcome {
+            TickOutcome::Continue => None,
+            TickOutcome::Survived => Some(Ending::Survived),
+            TickOutcome::Killed(killer) => Some(Ending::Killed {
+                killer: killer.clone(),
+                time: clock,
+            }),
+        }
+    }
+
+    fn parse(text: &str) -> Option<Ending> {
+        if text == "survived" {
+            return Some(Ending::Survived);
+        }
+
+        let rest = text.strip_prefix("killed by ")?;
+        let (killer, time) = rest.rsplit_once(" at ")?;
+        let (hours, minutes) = time.split_once(':')?;
+        let time = Clock {
+            time: hours.parse::<u32>().ok()? * 60 + minutes.parse::<u32>().ok()?,
+        };
+
+        Some(Ending::Killed {
+            killer: killer.to_string(),
+            time,
+        })
+    }
+}
+
+impl fmt::Display for Ending {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Ending::Survived => write!(f, "survived"),
+            Ending::Killed { killer, time } => write!(f, "killed by {killer} at {time}"),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct NightRecord {
+    pub seed: u64,
+    pub ai: AiMode,
+    pub difficulties: Vec<(String, u8)>,
+    pub ramp: Vec<RampStep>,
+    pub inputs: Vec<(u32, Command)>,
+    pub ending: Option<Ending>,
+}
+
+impl NightRecord {
+    pub fn from_night(night: &Night, ending: Option<Ending>) -> NightRecord {
+        NightRecord {
+            seed: night.seed,
+            ai: night.map.ai,
+            difficulties: night.start_levels(),
+            ramp: night.ramp.clone(),
+            inputs: night.inputs.clone(),
+            ending,
+        }
+    }
+
+    // plays the recorded inputs back without anyone at the keyboard, the
+    // layout and roster have to be the ones the night was recorded with
+    pub fn run(&self, layout: &Layout, roster: &Roster) -> Result<Ending, String> {
+        let mut animatronics = Vec::new();
+        for (name, difficulty) in self.difficulties.iter() {
+            let profile = roster
+                .find(name)
+                .ok_or_else(|| format!("{name} is not in the roster"))?;
+            animatronics.push(Animatronic::from_profile(profile.clone(), *difficulty));
+        }
+
+        let mut night = Night::new(layout.clone(), animatronics, self.seed);
+        night.map.set_ai(self.ai);
+        night.ramp = self.ramp.clone();
+        let mut inputs = self.inputs.iter().peekable();
+
+        loop {
+            while let Some((_, command)) = inputs.next_if(|(tick, _)| *tick == night.ticks) {
+                if !night.apply(*command) {
+                    return Err(format!("'{command}' was rejected on tick {}", night.ticks));
+                }
+            }
+
+            let outcome = night.tick();
+            if let Some(ending) = Ending::from_outcome(&outcome, night.clock) {
+                if let Some((tick, command)) = inputs.next() {
+                    return Err(format!(
+                        "night ended with '{command}' on tick {tick} still unplayed"
+                    ));
+                }
+                return Ok(ending);
+            }
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Replay {
+    pub version: String,
+    pub nights: Vec<NightRecord>,
+}
+
+impl Replay {
+    pub fn new() -> Replay {
+        Replay {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            nights: Vec::new(),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Replay, String> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty());
+
+        match lines.next() {
+            Some((_, REPLAY_HEADER)) => {}
+            _ => return Err(format!("missing '{REPLAY_HEADER}' header")),
+        }
+
+        let version = match lines.next() {
+            Some((_, line)) if line.starts_with("version ") => line["version ".len()..].to_string(),
+            _ => return Err(String::from("missing version line")),
+        };
+
+        let mut replay = Replay {
+            version,
+            nights: Vec::new(),
+        };
+        let mut current: Option<NightRecord> = None;
+
+        for (number, line) in lines {
+            let bad_line = || format!("line {number}: can't read '{line}'");
+
+            if let Some(rest) = line.strip_prefix("night ") {
+                if current.is_some() {
+                    return Err(format!("line {number}: previous night has no end"));
+                }
+                current = Some(parse_night_header(rest).ok_or_else(bad_line)?);
+            } else if let Some(rest) = line.strip_prefix("end") {
+                let mut night = current
+                    .take()
+                    .ok_or_else(|| format!("line {number}: end outside of a night"))?;
+                let rest = rest.trim();
+                if !rest.is_empty() {
+                    night.ending = Some(Ending::parse(rest).ok_or_else(bad_line)?);
+                }
+                replay.nights.push(night);
+            } else {
+                let night = current
+                    .as_mut()
+                    .ok_or_else(|| format!("line {number}: input outside of a night"))?;
+                let (tick, command) = line.split_once(' ').ok_or_else(bad_line)?;
+                let tick = tick.parse::<u32>().map_err(|_| bad_line())?;
+                let command = Command::parse(command).ok_or_else(bad_line)?;
+                night.inputs.push((tick, command));
+            }
+        }
+
+        if current.is_some() {
+            return Err(String::from("last night has no end"));
+        }
+
+        Ok(replay)
+    }
+}
+
+impl Default for Replay {
+    fn default() -> Replay {
+        Replay::new()
+    }
+}
+
+impl fmt::Display for Replay {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{REPLAY_HEADER}")?;
+        writeln!(f, "version {}", self.version)?;
+
+        for night in self.nights.iter() {
+            write!(f, "night seed {} ai {} difficulties", night.seed, night.ai)?;
+            for (name, difficulty) in night.difficulties.iter() {
+                write!(f, " {}={difficulty}", quoted(name))?;
+            }
+            if !night.ramp.is_empty() {
+                write!(f, " ramp")?;
+                for step in night.ramp.iter() {
+                    write!(f, " {step}")?;
+                }
+            }
+            writeln!(f)?;
+
+            for (tick, command) in night.inputs.iter() {
+                writeln!(f, "{tick} {command}")?;
+            }
+
+            match &night.ending {
+                Some(ending) => writeln!(f, "end {ending}")?,
+                None => writeln!(f, "end")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_night_header(text: &str) -> Option<NightRecord> {
+    let rest = text.strip_prefix("seed ")?;
+    let (seed, rest) = rest.split_once(' ')?;
+
+    // recordings from before the hunting AI don't say, they were classic
+    let (ai, rest) = match rest.strip_prefix("ai ") {
+        Some(rest) => {
+            let (ai, rest) = rest.split_once(' ')?;
+            (AiMode::parse(ai)?, rest)
+        }
+        None => (AiMode::Classic, rest),
+    };
+    let rest = rest.strip_prefix("difficulties")?;
+
+    let mut difficulties = Vec::new();
+    let mut ramp = Vec::new();
+    let words = split_words(rest)?;
+    let mut words = words.iter();
+    for pair in words.by_ref() {
+        if pair == "ramp" {
+            break;
+        }
+        let (name, difficulty) = pair.rsplit_once('=')?;
+        difficulties.push((unquoted(name).to_string(), difficulty.parse::<u8>().ok()?));
+    }
+    for step in words {
+        ramp.push(RampStep::parse(step)?);
+    }
+
+    Some(NightRecord {
+        seed: seed.parse::<u64>().ok()?,
+        ai,
+        difficulties,
+        ramp,
+        inputs: Vec::new(),
+        ending: None,
+    })
+}
+
+// whitespace separated, except inside quotes
+fn split_words(text: &str) -> Option<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut quoted = false;
+
+    for c in text.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                word.push(c);
+            }
+            c if c.is_whitespace() && !quoted => {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            c => word.push(c),
+        }
+    }
+
+    if quoted {
+        return None;
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    Some(words)
+}
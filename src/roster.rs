@@ -0,0 +1,305 @@
+use std::collections::{HashSet, VecDeque};
+
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::animatronics::{Animatronic, Tells};
+use crate::behavior::AiMode;
+use crate::clock::TICK_RATE;
+use crate::commands::Side;
+use crate::layout::Layout;
+use crate::map::Locations;
+
+const DEFAULT_ROSTER: &str = include_str!("../characters/roster.toml");
+
+// a name the way replay files write it, in quotes when it has a space
+pub(crate) fn quoted(name: &str) -> String {
+    if name.contains(char::is_whitespace) {
+        format!("\"{name}\"")
+    } else {
+        name.to_string()
+    }
+}
+
+pub(crate) fn unquoted(text: &str) -> &str {
+    text.strip_prefix('"')
+        .and_then(|text| text.strip_suffix('"'))
+        .unwrap_or(text)
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct RoomTells {
+    pub room: Locations,
+    pub tells: Vec<(Tells, u32)>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Path {
+    pub from: Locations,
+    pub to: Vec<Locations>,
+}
+
+// Pirate Cove rules: the character builds up through `stages` behind the
+// curtain, then sprints down the hallway of `door` and either gets into the
+// office or bangs on the door on the next tick
+#[derive(Clone, Debug, Deserialize)]
+pub struct Cove {
+    pub stages: u8,
+    pub door: Side,
+    pub bang_power: u8,
+}
+
+// how a character looks and behaves, everything in here comes from the
+// roster file so the movement code never has to check names
+#[derive(Clone, Debug, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    pub glyph: char,
+    pub start: Locations,
+    // AI level used when nobody picks one
+    pub difficulty: u8,
+    pub tells: Vec<(Tells, u32)>,
+    #[serde(default)]
+    pub room_tells: Vec<RoomTells>,
+    // only ever approach this door, both when missing
+    #[serde(default)]
+    pub door: Option<Side>,
+    #[serde(default)]
+    pub paths: Vec<Path>,
+    #[serde(default)]
+    pub power_out: bool,
+    #[serde(default)]
+    pub cove: Option<Cove>,
+    // sticks to this behavior whatever --ai says
+    #[serde(default)]
+    pub ai: Option<AiMode>,
+    // in-game minutes between chances to move, each one rolled against the
+    // AI level
+    #[serde(default = "default_move_every")]
+    pub move_every: u32,
+}
+
+fn default_move_every() -> u32 {
+    TICK_RATE
+}
+
+impl Profile {
+    // a plain character with the tells everyone shares
+    pub fn new(name: String, start: Locations, difficulty: u8) -> Profile {
+        Profile {
+            glyph: name.chars().next().unwrap_or('?'),
+            name,
+            start,
+            difficulty,
+            tells: vec![
+                (Tells::Noise, 2),
+                (Tells::Footsteps, 1),
+                (Tells::Static, 1),
+                (Tells::Visual, 1),
+            ],
+            room_tells: Vec::new(),
+            door: None,
+            paths: Vec::new(),
+            power_out: false,
+            cove: None,
+            ai: None,
+            move_every: TICK_RATE,
+        }
+    }
+
+    pub fn exits(&self, layout: &Layout, location: Locations) -> Vec<Locations> {
+        let exits = match self.paths.iter().find(|path| path.from == location) {
+            Some(path) => path.to.clone(),
+            None => layout.find_adjacent_room(location),
+        };
+
+        match self.door {
+            Some(side) => exits
+                .into_iter()
+                .filter(|exit| {
+                    !layout
+                        .doors
+                        .iter()
+                        .any(|door| door.side != side && door.room == *exit)
+                })
+                .collect(),
+            None => exits,
+        }
+    }
+
+    // the room this character is trying to get into
+    pub fn target(&self, layout: &Layout) -> Locations {
+        match self.door.and_then(|side| layout.door(side)) {
+            Some(door) => door.room,
+            None => layout.attack_room,
+        }
+    }
+
+    // like Layout::distance but only over the exits this character takes
+    pub fn distance(&self, layout: &Layout, from: Locations, to: Locations) -> Option<u8> {
+        let mut seen = vec![from];
+        let mut queue = VecDeque::from([(from, 0)]);
+
+        while let Some((location, distance)) = queue.pop_front() {
+            if location == to {
+                return Some(distance);
+            }
+
+            for exit in self.exits(layout, location) {
+                if !seen.contains(&exit) {
+                    seen.push(exit);
+                    queue.push_back((exit, distance + 1));
+                }
+            }
+        }
+
+        None
+    }
+
+    pub fn pick_tell(&self, location: Locations, rng: &mut impl Rng) -> Tells {
+        let tells = match self.room_tells.iter().find(|room| room.room == location) {
+            Some(room) => &room.tells,
+            None => &self.tells,
+        };
+
+        let total: u32 = tells.iter().map(|(_, weight)| weight).sum();
+        let mut roll = rng.random_range(0..total);
+        for (tell, weight) in tells.iter() {
+            if roll < *weight {
+                return *tell;
+            }
+            roll -= weight;
+        }
+
+        Tells::Visual
+    }
+
+    fn validate(&self, layout: &Layout) -> Result<(), String> {
+        let check_room = |location: Locations| match layout.room(location) {
+            Some(_) => Ok(()),
+            None => Err(format!("{}: {:?} is not on the map", self.name, location)),
+        };
+        let check_tells = |tells: &Vec<(Tells, u32)>| {
+            if tells.iter().map(|(_, weight)| weight).sum::<u32>() == 0 {
+                Err(format!(
+                    "{} needs at least one tell with a weight",
+                    self.name
+                ))
+            } else {
+                Ok(())
+            }
+        };
+
+        // names go into replay files, quoted when they have spaces
+        if self.name.trim().is_empty() || self.name.contains(['"', '\n', '\r']) {
+            return Err(format!(
+                "'{}' can't be a name, it needs letters and no quotes or line breaks",
+                self.name
+            ));
+        }
+
+        check_room(self.start)?;
+        check_tells(&self.tells)?;
+
+        if self.move_every == 0 {
+            return Err(format!("{}: move_every has to be at least 1", self.name));
+        }
+
+        for room in self.room_tells.iter() {
+            check_room(room.room)?;
+            check_tells(&room.tells)?;
+        }
+
+        for path in self.paths.iter() {
+            check_room(path.from)?;
+            for to in path.to.iter() {
+                check_room(*to)?;
+            }
+        }
+
+        if let Some(side) = self.door {
+            if layout.door(side).is_none() {
+                return Err(format!("{}: the map has no {} door", self.name, side));
+            }
+        }
+
+        if let Some(cove) = &self.cove {
+            if layout.door(cove.door).is_none() {
+                return Err(format!("{}: the map has no {} door", self.name, cove.door));
+            }
+            if cove.stages == 0 {
+                return Err(format!("{}: a cove needs at least one stage", self.name));
+            }
+            if cove.bang_power > 100 {
+                return Err(format!(
+                    "{}: bang_power is a percent of the battery, 100 at most",
+                    self.name
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Roster {
+    pub characters: Vec<Profile>,
+}
+
+impl Roster {
+    pub fn parse(text: &str, layout: &Layout) -> Result<Roster, String> {
+        let roster: Roster = toml::from_str(text).map_err(|err| err.to_string())?;
+        roster.validate(layout)?;
+        Ok(roster)
+    }
+
+    pub fn load(path: &str, layout: &Layout) -> Result<Roster, String> {
+        let text = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+        Roster::parse(&text, layout)
+    }
+
+    pub fn default_for(layout: &Layout) -> Result<Roster, String> {
+        Roster::parse(DEFAULT_ROSTER, layout)
+    }
+
+    pub fn find(&self, name: &str) -> Option<&Profile> {
+        self.characters
+            .iter()
+            .find(|profile| profile.name.eq_ignore_ascii_case(name))
+    }
+
+    // everyone at their default AI level
+    pub fn animatronics(&self) -> Vec<Animatronic> {
+        self.characters
+            .iter()
+            .map(|profile| Animatronic::from_profile(profile.clone(), profile.difficulty))
+            .collect()
+    }
+
+    pub fn validate(&self, layout: &Layout) -> Result<(), String> {
+        if self.characters.is_empty() {
+            return Err(String::from("the roster is empty"));
+        }
+
+        let mut names = HashSet::new();
+        for profile in self.characters.iter() {
+            if !names.insert(profile.name.to_lowercase()) {
+                return Err(format!("{} is in the roster twice", profile.name));
+            }
+            profile.validate(layout)?;
+        }
+
+        if self
+            .characters
+            .iter()
+            .filter(|profile| profile.power_out)
+            .count()
+            > 1
+        {
+            return Err(String::from("only one character can have power_out"));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,179 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::animatronics::MAX_DIFFICULTY;
+
+pub const DEFAULT_SLOT: &str = "default";
+
+// campaign progress for one save slot
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Save {
+    // the night to play on Continue, 0 when no campaign is going
+    #[serde(default)]
+    pub night: u8,
+    #[serde(default)]
+    pub nights_completed: u8,
+    // the campaign rng starts over from this on Continue, written as text
+    // since toml integers stop at i64
+    #[serde(default, with = "seed_text")]
+    pub seed: u64,
+    // one star each, they can be earned in any order
+    #[serde(default)]
+    pub five_nights: bool,
+    #[serde(default)]
+    pub night_six_beaten: bool,
+    #[serde(default)]
+    pub max_custom: bool,
+    // saves from before the stars above only had a count, read on load and
+    // never written again
+    #[serde(default, skip_serializing)]
+    stars: u8,
+    #[serde(default)]
+    pub custom_night: bool,
+    #[serde(default)]
+    pub night_six: bool,
+}
+
+impl Save {
+    pub fn in_progress(&self) -> bool {
+        self.night > 0
+    }
+
+    pub fn stars(&self) -> u8 {
+        [self.five_nights, self.night_six_beaten, self.max_custom]
+            .iter()
+            .filter(|star| **star)
+            .count() as u8
+    }
+
+    // remembers where the campaign is before `night` starts, the levels
+    // come from the campaign table
+    pub fn start_night(&mut self, night: u8, seed: u64) {
+        self.night = night;
+        self.seed = seed;
+    }
+
+    // `nights` is how long the campaign is, beating the last one unlocks
+    // the extras
+    pub fn complete_night(&mut self, night: u8, nights: u8) {
+        self.nights_completed = self.nights_completed.max(night);
+
+        if night >= nights {
+            self.night = 0;
+            self.five_nights = true;
+            self.custom_night = true;
+            self.night_six = true;
+        }
+    }
+
+    pub fn complete_night_six(&mut self) {
+        self.night_six_beaten = true;
+    }
+
+    // the last star is for a Custom Night with everyone at the top level,
+    // `levels` are the ones the night started on, see Night::start_levels
+    pub fn complete_custom_night(&mut self, levels: &[(String, u8)]) {
+        if !levels.is_empty() && levels.iter().all(|(_, level)| *level == MAX_DIFFICULTY) {
+            self.max_custom = true;
+        }
+    }
+}
+
+mod seed_text {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(seed: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(seed)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+// a directory of save slots, one toml file each
+pub struct Saves {
+    pub dir: PathBuf,
+}
+
+impl Saves {
+    pub fn new(dir: PathBuf) -> Saves {
+        Saves { dir }
+    }
+
+    // $XDG_DATA_HOME/fnafterminal, or ~/.local/share/fnafterminal without it
+    pub fn default_dir() -> Option<PathBuf> {
+        let data = match std::env::var_os("XDG_DATA_HOME").filter(|dir| !dir.is_empty()) {
+            Some(dir) => PathBuf::from(dir),
+            None => PathBuf::from(std::env::var_os("HOME")?).join(".local/share"),
+        };
+        Some(data.join("fnafterminal"))
+    }
+
+    pub fn valid_slot(slot: &str) -> bool {
+        !slot.is_empty()
+            && slot
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    }
+
+    fn path(&self, slot: &str) -> Result<PathBuf, String> {
+        if !Saves::valid_slot(slot) {
+            return Err(format!(
+                "'{slot}' is not a slot name, use letters, numbers, - and _"
+            ));
+        }
+        Ok(self.dir.join(format!("{slot}.toml")))
+    }
+
+    pub fn slots(&self) -> Vec<String> {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+
+        let mut slots: Vec<String> = entries
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                if path.extension()? != "toml" {
+                    return None;
+                }
+                Some(path.file_stem()?.to_str()?.to_string())
+            })
+            .filter(|slot| Saves::valid_slot(slot))
+            .collect();
+        slots.sort();
+        slots
+    }
+
+    // an empty save for a slot that was never written
+    pub fn load(&self, slot: &str) -> Result<Save, String> {
+        let path = self.path(slot)?;
+        if !path.exists() {
+            return Ok(Save::default());
+        }
+
+        let text = std::fs::read_to_string(&path).map_err(|err| err.to_string())?;
+        let mut save: Save = toml::from_str(&text).map_err(|err| err.to_string())?;
+
+        // an old count could only be earned in order
+        let stars = std::mem::take(&mut save.stars);
+        save.five_nights |= stars >= 1;
+        save.night_six_beaten |= stars >= 2;
+        save.max_custom |= stars >= 3;
+        Ok(save)
+    }
+
+    pub fn store(&self, slot: &str, save: &Save) -> Result<(), String> {
+        let path = self.path(slot)?;
+        std::fs::create_dir_all(&self.dir).map_err(|err| err.to_string())?;
+        let text = toml::to_string(save).map_err(|err| err.to_string())?;
+        std::fs::write(path, text).map_err(|err| err.to_string())
+    }
+
+    pub fn delete(&self, slot: &str) -> Result<(), String> {
+        let path = self.path(slot)?;
+        std::fs::remove_file(path).map_err(|err| err.to_string())
+    }
+}
@@ -0,0 +1,175 @@
+use std::fmt;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::animatronics::Animatronic;
+use crate::behavior::AiMode;
+use crate::clock::END_TIME;
+use crate::layout::Layout;
+use crate::night::{Night, TickOutcome};
+use crate::roster::Roster;
+use crate::strategy::{Bot, Observation, Strategy};
+
+const HOURS: usize = (END_TIME / 60) as usize;
+
+// everything counted over a batch of nights at one set of AI levels
+#[derive(Clone, Debug, Default)]
+pub struct Summary {
+    // name and AI level of everyone in the night
+    pub levels: Vec<(String, u8)>,
+    pub nights: u32,
+    pub survived: u32,
+    // deaths by the hour they happened in, 12 AM first
+    pub deaths_by_hour: [u32; HOURS],
+    pub killers: Vec<(String, u32)>,
+    // battery left at the end of every survived night
+    pub power_left: Vec<u32>,
+}
+
+impl Summary {
+    pub fn record(&mut self, night: &Night, outcome: &TickOutcome) {
+        self.nights += 1;
+
+        match outcome {
+            TickOutcome::Continue => {}
+            TickOutcome::Survived => {
+                self.survived += 1;
+                self.power_left.push(night.battery.percent());
+            }
+            TickOutcome::Killed(killer) => {
+                let hour = (night.clock.time / 60) as usize;
+                self.deaths_by_hour[hour.min(HOURS - 1)] += 1;
+
+                match self.killers.iter_mut().find(|(name, _)| name == killer) {
+                    Some((_, kills)) => *kills += 1,
+                    None => self.killers.push((killer.clone(), 1)),
+                }
+            }
+        }
+    }
+
+    pub fn survival_rate(&self) -> f64 {
+        if self.nights == 0 {
+            return 0.0;
+        }
+        self.survived as f64 / self.nights as f64
+    }
+
+    pub fn average_power_left(&self) -> Option<f64> {
+        if self.power_left.is_empty() {
+            return None;
+        }
+        Some(self.power_left.iter().sum::<u32>() as f64 / self.power_left.len() as f64)
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let levels: Vec<String> = self
+            .levels
+            .iter()
+            .map(|(name, level)| format!("{name} {level}"))
+            .collect();
+        writeln!(f, "{}", levels.join(", "))?;
+
+        writeln!(
+            f,
+            "\t survived: {}/{} ({:.1}%)",
+            self.survived,
+            self.nights,
+            self.survival_rate() * 100.0
+        )?;
+
+        let hours: Vec<String> = self
+            .deaths_by_hour
+            .iter()
+            .enumerate()
+            .map(|(hour, deaths)| {
+                let hour = if hour == 0 { 12 } else { hour };
+                format!("{hour} AM {deaths}")
+            })
+            .collect();
+        writeln!(f, "\t deaths by hour: {}", hours.join(", "))?;
+
+        let mut killers = self.killers.clone();
+        killers.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        let killers: Vec<String> = killers
+            .iter()
+            .map(|(name, kills)| format!("{name} {kills}"))
+            .collect();
+        if killers.is_empty() {
+            writeln!(f, "\t killers: nobody")?;
+        } else {
+            writeln!(f, "\t killers: {}", killers.join(", "))?;
+        }
+
+        match self.average_power_left() {
+            Some(average) => write!(
+                f,
+                "\t power left at 6 AM: {average:.1}% on average, {}% at worst",
+                self.power_left.iter().min().unwrap_or(&0)
+            ),
+            None => write!(f, "\t power left at 6 AM: nobody made it"),
+        }
+    }
+}
+
+// plays one night to the end without a frontend, the strategy gets
+// everything the night says through its observations
+pub fn run_night(night: &mut Night, strategy: &mut dyn Strategy) -> TickOutcome {
+    let messages = night.take_messages();
+    let mut seen = Observation::new(night, messages, None);
+
+    loop {
+        night.apply(strategy.choose(&seen));
+        let camera = night.take_camera_views().pop();
+        let outcome = night.tick();
+
+        if outcome != TickOutcome::Continue {
+            return outcome;
+        }
+        let messages = night.take_messages();
+        seen = Observation::new(night, messages, camera);
+    }
+}
+
+// plays `nights` nights with the roster at `levels`, in roster order. every
+// batch with the same seed sees the same night seeds, so settings are
+// compared on the same luck
+pub fn simulate(
+    layout: &Layout,
+    roster: &Roster,
+    levels: &[u8],
+    ai: AiMode,
+    bot: Bot,
+    nights: u32,
+    seed: u64,
+) -> Summary {
+    let animatronics: Vec<Animatronic> = roster
+        .characters
+        .iter()
+        .zip(levels.iter())
+        .map(|(profile, level)| Animatronic::from_profile(profile.clone(), *level))
+        .collect();
+
+    let mut summary = Summary {
+        levels: animatronics
+            .iter()
+            .map(|anim| (anim.name.clone(), anim.difficulty))
+            .collect(),
+        ..Summary::default()
+    };
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut night = Night::new(layout.clone(), animatronics, seed);
+    night.map.set_ai(ai);
+
+    for _ in 0..nights {
+        night.reset(rng.random());
+        let outcome = run_night(&mut night, bot.strategy().as_mut());
+        summary.record(&night, &outcome);
+    }
+
+    summary
+}
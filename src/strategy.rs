@@ -0,0 +1,252 @@
+use std::fmt;
+
+use crate::clock::{Clock, END_TIME, TICK_RATE};
+use crate::commands::{Command, Side};
+use crate::night::Night;
+use crate::power::{DRAIN_PER_BAR, MAX_USAGE, UNIT};
+
+// what the guard knows at the start of a turn, only ever what the office
+// shows: the clock, the power meter, the buttons, whatever the lights and
+// the camera picked up and what can be heard
+#[derive(Clone, Debug)]
+pub struct Observation {
+    pub clock: Clock,
+    pub battery: u32,
+    pub usage: usize,
+    pub power_on: bool,
+    // the doors the office has, and which of them are shut
+    pub doors: Vec<Side>,
+    pub closed_doors: Vec<Side>,
+    pub lights_on: Vec<Side>,
+    // who a light that is on shows in the hallway
+    pub seen: Vec<(Side, String)>,
+    // someone right outside a door, heard and not seen
+    pub breathing: Vec<Side>,
+    // everything the night said since the last turn
+    pub messages: Vec<String>,
+    // the last camera view of the last turn, if the camera was used
+    pub camera: Option<String>,
+}
+
+impl Observation {
+    pub fn new(night: &Night, messages: Vec<String>, camera: Option<String>) -> Observation {
+        let layout = &night.map.layout;
+        let anims = &night.map.anim_states;
+
+        let mut seen = Vec::new();
+        let mut breathing = Vec::new();
+        for door in layout.doors.iter() {
+            if night.light_on(door.side) {
+                for anim in anims.iter().filter(|anim| anim.location == door.hallway) {
+                    seen.push((door.side, anim.name.clone()));
+                }
+            }
+            if anims.iter().any(|anim| anim.location == door.room) {
+                breathing.push(door.side);
+            }
+        }
+
+        Observation {
+            clock: night.clock,
+            battery: night.battery.percent(),
+            usage: night.battery.usage(),
+            power_on: night.battery.is_online,
+            doors: layout.doors.iter().map(|door| door.side).collect(),
+            closed_doors: layout
+                .doors
+                .iter()
+                .map(|door| door.side)
+                .filter(|side| night.door_closed(*side))
+                .collect(),
+            lights_on: layout
+                .doors
+                .iter()
+                .map(|door| door.side)
+                .filter(|side| night.light_on(*side))
+                .collect(),
+            seen,
+            breathing,
+            messages,
+            camera,
+        }
+    }
+
+    pub fn door_closed(&self, side: Side) -> bool {
+        self.closed_doors.contains(&side)
+    }
+
+    pub fn light_on(&self, side: Side) -> bool {
+        self.lights_on.contains(&side)
+    }
+
+    pub fn breathing(&self, side: Side) -> bool {
+        self.breathing.contains(&side)
+    }
+
+    pub fn seen_at(&self, side: Side) -> bool {
+        self.seen.iter().any(|(seen, _)| *seen == side)
+    }
+
+    // whether `extra` more usage bars for a tick still leave enough power
+    // to sit out the rest of the night at one bar
+    pub fn affordable(&self, extra: usize) -> bool {
+        let minutes_left = END_TIME.saturating_sub(self.clock.time);
+        let bars = (self.usage + extra).min(MAX_USAGE) as u32;
+        let needed = bars * DRAIN_PER_BAR * TICK_RATE
+            + DRAIN_PER_BAR * minutes_left.saturating_sub(TICK_RATE);
+        self.battery * UNIT >= needed
+    }
+}
+
+// an automated guard, picks the command for every turn from what it can
+// see and hear
+pub trait Strategy {
+    fn choose(&mut self, seen: &Observation) -> Command;
+}
+
+// never touches anything
+pub struct SitForever;
+
+impl Strategy for SitForever {
+    fn choose(&mut self, _seen: &Observation) -> Command {
+        Command::Sit
+    }
+}
+
+// shuts every door straight away and waits for dawn
+pub struct ShutDoors;
+
+impl Strategy for ShutDoors {
+    fn choose(&mut self, seen: &Observation) -> Command {
+        match seen.doors.iter().find(|side| !seen.door_closed(**side)) {
+            Some(side) if seen.power_on => Command::Close(*side),
+            _ => Command::Sit,
+        }
+    }
+}
+
+// shuts a door while someone breathes behind it and opens it again once
+// they are gone
+pub struct CloseOnBreathing;
+
+impl Strategy for CloseOnBreathing {
+    fn choose(&mut self, seen: &Observation) -> Command {
+        for side in seen.doors.iter() {
+            if seen.breathing(*side) && !seen.door_closed(*side) {
+                return Command::Close(*side);
+            }
+        }
+
+        for side in seen.doors.iter() {
+            if seen.door_closed(*side) && !seen.breathing(*side) {
+                return Command::Open(*side);
+            }
+        }
+
+        Command::Sit
+    }
+}
+
+// pulls the camera up every other turn, which is all it takes to slow
+// down anyone who minds being watched
+#[derive(Default)]
+pub struct CameraEveryOtherTurn {
+    turn: u32,
+}
+
+impl Strategy for CameraEveryOtherTurn {
+    fn choose(&mut self, _seen: &Observation) -> Command {
+        self.turn += 1;
+        if self.turn.is_multiple_of(2) {
+            Command::Sit
+        } else {
+            Command::Camera
+        }
+    }
+}
+
+// shuts a door on whatever it hears or sees at it, checks the hallways
+// with the lights in between, and skips anything the battery can't pay
+// for until 6 AM
+#[derive(Default)]
+pub struct PowerBudget {
+    turn: u32,
+}
+
+impl Strategy for PowerBudget {
+    fn choose(&mut self, seen: &Observation) -> Command {
+        self.turn += 1;
+        let threat = |side: Side| seen.breathing(side) || seen.seen_at(side);
+
+        for side in seen.doors.iter() {
+            if threat(*side) && !seen.door_closed(*side) && seen.affordable(1) {
+                return Command::Close(*side);
+            }
+        }
+
+        for side in seen.doors.iter() {
+            if seen.door_closed(*side) && !threat(*side) {
+                return Command::Open(*side);
+            }
+        }
+
+        // a light only stays on for the one turn it takes to look
+        for side in seen.doors.iter() {
+            if seen.light_on(*side) {
+                return Command::LightOff(*side);
+            }
+        }
+
+        if self.turn.is_multiple_of(2) && !seen.doors.is_empty() && seen.affordable(1) {
+            let side = seen.doors[(self.turn as usize / 2) % seen.doors.len()];
+            return Command::LightOn(side);
+        }
+
+        Command::Sit
+    }
+}
+
+// the built in bots, by the name --strategy knows them by
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Bot {
+    Sit,
+    Doors,
+    Breathing,
+    Camera,
+    Budget,
+}
+
+impl Bot {
+    pub fn parse(text: &str) -> Option<Bot> {
+        match text.trim().to_lowercase().as_str() {
+            "sit" => Some(Bot::Sit),
+            "doors" => Some(Bot::Doors),
+            "breathing" => Some(Bot::Breathing),
+            "camera" => Some(Bot::Camera),
+            "budget" => Some(Bot::Budget),
+            _ => None,
+        }
+    }
+
+    pub fn strategy(&self) -> Box<dyn Strategy> {
+        match self {
+            Bot::Sit => Box::new(SitForever),
+            Bot::Doors => Box::new(ShutDoors),
+            Bot::Breathing => Box::new(CloseOnBreathing),
+            Bot::Camera => Box::new(CameraEveryOtherTurn::default()),
+            Bot::Budget => Box::new(PowerBudget::default()),
+        }
+    }
+}
+
+impl fmt::Display for Bot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Bot::Sit => write!(f, "sit"),
+            Bot::Doors => write!(f, "doors"),
+            Bot::Breathing => write!(f, "breathing"),
+            Bot::Camera => write!(f, "camera"),
+            Bot::Budget => write!(f, "budget"),
+        }
+    }
+}
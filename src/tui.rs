@@ -0,0 +1,290 @@
+use std::io;
+use std::time::{Duration, Instant};
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Gauge, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+
+use fnaf::power::MAX_USAGE;
+use fnaf::{Clock, Keymap, Night, TickOutcome};
+
+// full-screen frontend, same engine as the line-mode one. the camera panel
+// keeps the last thing the camera showed and everything else said during
+// the night goes into the log
+struct Screen {
+    title: String,
+    log: Vec<String>,
+    camera: Option<(Clock, String)>,
+    input: String,
+}
+
+impl Screen {
+    fn new(title: &str, keys: Option<&Keymap>) -> Screen {
+        let mut log = vec![String::from(
+            "Type a command and press Enter, Esc leaves the game.",
+        )];
+        if let Some(keys) = keys {
+            log.push(format!("Keys: {keys}"));
+        }
+
+        Screen {
+            title: String::from(title),
+            log,
+            camera: None,
+            input: String::new(),
+        }
+    }
+
+    fn collect(&mut self, night: &mut Night) {
+        if let Some(view) = night.take_camera_views().pop() {
+            self.camera = Some((night.clock, view));
+        }
+        self.log.extend(night.take_messages());
+    }
+
+    fn draw(&self, frame: &mut Frame, night: &Night) {
+        let [status, middle, log, input] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(10),
+            Constraint::Length(10),
+            Constraint::Length(3),
+        ])
+        .areas(frame.area());
+        let [office, camera] =
+            Layout::horizontal([Constraint::Length(30), Constraint::Min(30)]).areas(middle);
+
+        self.draw_status(frame, status, night);
+        self.draw_office(frame, office, night);
+        self.draw_camera(frame, camera);
+        self.draw_log(frame, log);
+
+        frame.render_widget(
+            Paragraph::new(format!("> {}", self.input)).block(Block::bordered().title("Command")),
+            input,
+        );
+        frame.set_cursor_position((input.x + 3 + self.input.len() as u16, input.y + 1));
+    }
+
+    fn draw_status(&self, frame: &mut Frame, area: Rect, night: &Night) {
+        let [clock, battery, usage] = Layout::horizontal([
+            Constraint::Length(30),
+            Constraint::Min(20),
+            Constraint::Length(20),
+        ])
+        .areas(area);
+
+        frame.render_widget(
+            Paragraph::new(format!("{}  {}", self.title, night.clock)).block(Block::bordered()),
+            clock,
+        );
+
+        let power = night.battery.percent();
+        let color = match power {
+            51.. => Color::Green,
+            21..=50 => Color::Yellow,
+            _ => Color::Red,
+        };
+        frame.render_widget(
+            Gauge::default()
+                .block(Block::bordered().title("Battery"))
+                .gauge_style(Style::new().fg(color))
+                .ratio(power as f64 / 100.0)
+                .label(format!("{power}%")),
+            battery,
+        );
+
+        let bars = night.battery.usage();
+        frame.render_widget(
+            Paragraph::new(format!(
+                "{}{}",
+                "■ ".repeat(bars),
+                "□ ".repeat(MAX_USAGE - bars)
+            ))
+            .block(Block::bordered().title("Usage")),
+            usage,
+        );
+    }
+
+    fn draw_office(&self, frame: &mut Frame, area: Rect, night: &Night) {
+        let door = |closed: bool| match closed {
+            true => Span::from("CLOSED").red().bold(),
+            false => Span::from("open").green(),
+        };
+        let light = |on: bool| match on {
+            true => Span::from("ON").yellow().bold(),
+            false => Span::from("off").dark_gray(),
+        };
+
+        let mut lines = vec![
+            Line::from(vec![
+                Span::from("Left Door    "),
+                door(night.map.left_door_closed),
+            ]),
+            Line::from(vec![
+                Span::from("Right Door   "),
+                door(night.map.right_door_closed),
+            ]),
+            Line::from(""),
+            Line::from(vec![
+                Span::from("Left Light   "),
+                light(night.map.left_light_on),
+            ]),
+            Line::from(vec![
+                Span::from("Right Light  "),
+                light(night.map.right_light_on),
+            ]),
+        ];
+        if !night.battery.is_online {
+            lines.push(Line::from(""));
+            lines.push(Line::from("POWER OUT").red().bold());
+        }
+
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title("Office")),
+            area,
+        );
+    }
+
+    fn draw_camera(&self, frame: &mut Frame, area: Rect) {
+        let (title, view) = match &self.camera {
+            Some((time, view)) => (format!("Camera ({time})"), view.as_str()),
+            None => (String::from("Camera"), "\n Type 'camera' to look."),
+        };
+
+        frame.render_widget(
+            Paragraph::new(view).block(Block::bordered().title(title)),
+            area,
+        );
+    }
+
+    fn draw_log(&self, frame: &mut Frame, area: Rect) {
+        // newest at the bottom, older lines scroll off the top
+        let height = area.height.saturating_sub(2) as usize;
+        let start = self.log.len().saturating_sub(height);
+        let lines: Vec<Line> = self.log[start..]
+            .iter()
+            .map(|line| Line::from(line.as_str()))
+            .collect();
+
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title("Log")),
+            area,
+        );
+    }
+}
+
+// plays the night in the TUI, one tick per command or every `tick_length`
+// in real time. `keys` turns on single-key controls
+pub fn play_night(
+    night: &mut Night,
+    title: &str,
+    tick_length: Option<Duration>,
+    keys: Option<&Keymap>,
+) -> io::Result<TickOutcome> {
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal, night, title, tick_length, keys);
+    ratatui::restore();
+    result
+}
+
+fn run(
+    terminal: &mut DefaultTerminal,
+    night: &mut Night,
+    title: &str,
+    tick_length: Option<Duration>,
+    keys: Option<&Keymap>,
+) -> io::Result<TickOutcome> {
+    let mut screen = Screen::new(title, keys);
+    let mut next_tick = tick_length.map(|length| Instant::now() + length);
+
+    loop {
+        terminal.draw(|frame| screen.draw(frame, night))?;
+
+        let mut tick_now = next_tick.is_some_and(|at| Instant::now() >= at);
+        let timeout = match next_tick {
+            Some(at) => at.saturating_duration_since(Instant::now()),
+            None => Duration::from_secs(60),
+        };
+
+        if !tick_now && event::poll(timeout)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+
+                // a bound key acts right away unless a command is being typed
+                let bound = match key.code {
+                    KeyCode::Char(c) if screen.input.is_empty() => {
+                        keys.and_then(|keys| keys.command(c))
+                    }
+                    _ => None,
+                };
+
+                let line = match key.code {
+                    KeyCode::Esc => quit(),
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => quit(),
+                    _ if bound.is_some() => bound.map(|command| command.to_string()),
+                    KeyCode::Char(c) => {
+                        screen.input.push(c);
+                        None
+                    }
+                    KeyCode::Backspace => {
+                        screen.input.pop();
+                        None
+                    }
+                    KeyCode::Enter => Some(std::mem::take(&mut screen.input)),
+                    _ => None,
+                };
+
+                if let Some(line) = line {
+                    screen.log.push(format!("> {line}"));
+                    let accepted = night.enter(&line);
+                    screen.collect(night);
+
+                    // without a timer every command is a turn
+                    tick_now = accepted && next_tick.is_none();
+                }
+            }
+        }
+
+        if !tick_now {
+            continue;
+        }
+        if let (Some(at), Some(length)) = (next_tick.as_mut(), tick_length) {
+            *at += length;
+        }
+
+        let outcome = night.tick();
+        screen.collect(night);
+
+        let ending = match &outcome {
+            TickOutcome::Continue => continue,
+            TickOutcome::Survived => String::from("You survived the night!"),
+            TickOutcome::Killed(_) => night.death_message(),
+        };
+        screen.log.push(ending);
+        screen.log.push(String::from("Press any key to continue."));
+        terminal.draw(|frame| screen.draw(frame, night))?;
+        wait_for_key()?;
+
+        return Ok(outcome);
+    }
+}
+
+fn wait_for_key() -> io::Result<()> {
+    loop {
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press {
+                return Ok(());
+            }
+        }
+    }
+}
+
+fn quit() -> ! {
+    ratatui::restore();
+    std::process::exit(0);
+}
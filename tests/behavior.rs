@@ -0,0 +1,209 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use fnaf::{
+    AiMode, Animatronic, Behavior, CameraShy, Command, Door, DoorCamper, GoalSeeking, Layout,
+    Locations, Move, Night, Profile, RandomWalk, Side, TickOutcome, WorldView,
+};
+
+mod common;
+use common::roster;
+
+fn view<'a>(
+    layout: &'a Layout,
+    profile: &'a Profile,
+    location: Locations,
+    difficulty: u8,
+    closed_doors: &'a [Door],
+) -> WorldView<'a> {
+    WorldView {
+        layout,
+        profile,
+        location,
+        difficulty,
+        closed_doors,
+        camera_used: false,
+    }
+}
+
+// every room `behavior` walks through from `from` until it stays put or
+// gets to `to`
+fn walk(
+    behavior: &mut dyn Behavior,
+    profile: &Profile,
+    from: Locations,
+    to: Locations,
+) -> Vec<Locations> {
+    let layout = Layout::default();
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut rooms = vec![from];
+
+    while *rooms.last().unwrap() != to && rooms.len() < 20 {
+        let ctx = view(&layout, profile, *rooms.last().unwrap(), 20, &[]);
+        match behavior.choose_move(&ctx, &mut rng) {
+            Move::To(next) => rooms.push(next),
+            Move::Stay => break,
+        }
+    }
+    rooms
+}
+
+#[test]
+fn at_the_top_level_hunting_takes_the_shortest_way_in() {
+    let layout = Layout::default();
+    let bonnie = roster().find("Bonnie").unwrap().clone();
+
+    let rooms = walk(
+        &mut GoalSeeking,
+        &bonnie,
+        Locations::ShowStage,
+        layout.attack_room,
+    );
+    assert_eq!(
+        rooms,
+        vec![
+            Locations::ShowStage,
+            Locations::DiningAreaC,
+            Locations::Restrooms,
+            Locations::HallwayL,
+            Locations::SecurityOfficeStaticL,
+            Locations::SecurityOfficeAttack,
+        ]
+    );
+}
+
+#[test]
+fn hunting_for_one_door_goes_around_the_other() {
+    let chica = roster().find("Chica").unwrap().clone();
+    assert_eq!(chica.door, Some(Side::Right));
+
+    let rooms = walk(
+        &mut GoalSeeking,
+        &chica,
+        Locations::ShowStage,
+        Locations::SecurityOfficeStaticR,
+    );
+    assert_eq!(rooms.len(), 6, "{rooms:?}");
+    assert!(rooms.contains(&Locations::Kitchen));
+    assert!(!rooms.contains(&Locations::HallwayL));
+}
+
+#[test]
+fn a_hunter_gets_in_one_room_a_turn() {
+    let profile = roster().find("Bonnie").unwrap().clone();
+    let mut night = Night::new(
+        Layout::default(),
+        vec![Animatronic::from_profile(profile, 20)],
+        1,
+    );
+    night.map.set_ai(AiMode::Hunt);
+
+    for _ in 0..5 {
+        assert!(night.apply(Command::Sit));
+        assert_eq!(night.tick(), TickOutcome::Continue);
+    }
+    assert_eq!(
+        night.map.anim_states[0].location,
+        Locations::SecurityOfficeAttack
+    );
+    assert_eq!(night.tick(), TickOutcome::Killed(String::from("Bonnie")));
+}
+
+#[test]
+fn a_random_walk_tries_every_exit_and_nothing_else() {
+    let layout = Layout::default();
+    let bonnie = roster().find("Bonnie").unwrap().clone();
+    let ctx = view(&layout, &bonnie, Locations::DiningAreaC, 20, &[]);
+    let mut rng = StdRng::seed_from_u64(1);
+
+    let mut picked = Vec::new();
+    for _ in 0..200 {
+        let Move::To(next) = RandomWalk.choose_move(&ctx, &mut rng) else {
+            panic!("a random walk never stays");
+        };
+        if !picked.contains(&next) {
+            picked.push(next);
+        }
+    }
+
+    let mut exits = ctx.exits();
+    exits.sort_by_key(|room| format!("{room:?}"));
+    picked.sort_by_key(|room| format!("{room:?}"));
+    assert_eq!(picked, exits);
+}
+
+#[test]
+fn a_camper_waits_at_its_closed_door() {
+    let layout = Layout::default();
+    let bonnie = roster().find("Bonnie").unwrap().clone();
+    assert_eq!(bonnie.door, Some(Side::Left));
+    let left = *layout.door(Side::Left).unwrap();
+    let mut rng = StdRng::seed_from_u64(1);
+
+    let closed = [left];
+    let ctx = view(&layout, &bonnie, Locations::HallwayL, 20, &closed);
+    assert_eq!(DoorCamper.choose_move(&ctx, &mut rng), Move::Stay);
+
+    let ctx = view(&layout, &bonnie, Locations::HallwayL, 20, &[]);
+    assert_eq!(DoorCamper.choose_move(&ctx, &mut rng), Move::To(left.room));
+
+    let rooms = walk(
+        &mut DoorCamper,
+        &bonnie,
+        Locations::ShowStage,
+        Locations::HallwayL,
+    );
+    assert_eq!(rooms.len(), 4, "{rooms:?}");
+}
+
+#[test]
+fn camera_shy_freezes_while_watched() {
+    let layout = Layout::default();
+    let bonnie = roster().find("Bonnie").unwrap().clone();
+    let mut rng = StdRng::seed_from_u64(1);
+
+    let mut ctx = view(&layout, &bonnie, Locations::HallwayL, 20, &[]);
+    ctx.camera_used = true;
+    assert_eq!(CameraShy.choose_move(&ctx, &mut rng), Move::Stay);
+
+    ctx.camera_used = false;
+    assert_eq!(
+        CameraShy.choose_move(&ctx, &mut rng),
+        Move::To(Locations::SecurityOfficeStaticL)
+    );
+}
+
+#[test]
+fn ai_modes_by_name() {
+    for ai in [
+        AiMode::Classic,
+        AiMode::Hunt,
+        AiMode::Camper,
+        AiMode::CameraShy,
+    ] {
+        assert_eq!(AiMode::parse(&ai.to_string()), Some(ai));
+    }
+    assert_eq!(AiMode::parse(" HUNT "), Some(AiMode::Hunt));
+    assert_eq!(AiMode::parse("smart"), None);
+}
+
+#[test]
+fn the_roster_can_pin_a_behavior() {
+    let mut profile = roster().find("Bonnie").unwrap().clone();
+    profile.ai = Some(AiMode::CameraShy);
+    let mut night = Night::new(
+        Layout::default(),
+        vec![Animatronic::from_profile(profile, 20)],
+        1,
+    );
+    night.map.set_ai(AiMode::Classic);
+    night.map.anim_states[0].location = Locations::HallwayL;
+
+    // the camera keeps a camera-shy Bonnie where he is, a classic one would
+    // walk off
+    for _ in 0..5 {
+        assert!(night.apply(Command::Camera));
+        assert_eq!(night.tick(), TickOutcome::Continue);
+        assert_eq!(night.map.anim_states[0].location, Locations::HallwayL);
+    }
+}
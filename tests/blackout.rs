@@ -0,0 +1,77 @@
+use fnaf::{Command, Layout, Night, PowerOut, TickOutcome};
+
+mod common;
+use common::roster;
+
+// a night with the default cast in reverse, so the power_out character is
+// not the first one, and the battery about to run dry
+fn night_at(time: u32, seed: u64) -> Night {
+    let layout = Layout::default();
+    let mut anims = roster().animatronics();
+    anims.reverse();
+
+    let mut night = Night::new(layout, anims, seed);
+    night.clock.time = time;
+    night.battery.charge = 1;
+    night
+}
+
+fn play_out(night: &mut Night) -> TickOutcome {
+    loop {
+        assert!(night.apply(Command::Sit));
+        match night.tick() {
+            TickOutcome::Continue => {}
+            outcome => return outcome,
+        }
+    }
+}
+
+#[test]
+fn power_out_character_comes_whatever_the_roster_order() {
+    for seed in 0..20 {
+        let mut night = night_at(0, seed);
+        assert_eq!(
+            play_out(&mut night),
+            TickOutcome::Killed(String::from("Freddy"))
+        );
+        assert_eq!(night.blackout.state, PowerOut::Attack);
+    }
+}
+
+#[test]
+fn dawn_ends_the_blackout() {
+    let mut night = night_at(5 * 60 + 45, 3);
+    assert_eq!(play_out(&mut night), TickOutcome::Survived);
+    assert!(!night.battery.is_online);
+    assert_eq!(night.blackout.state, PowerOut::LightsOut);
+}
+
+#[test]
+fn blackout_goes_through_every_stage_in_order() {
+    let mut night = night_at(0, 7);
+    let mut seen = vec![night.blackout.state];
+
+    loop {
+        let outcome = night.tick();
+        let state = night.blackout.state;
+        if seen.last() != Some(&state) {
+            seen.push(state);
+        }
+        if outcome != TickOutcome::Continue {
+            break;
+        }
+    }
+
+    let stage = |state: &PowerOut| match state {
+        PowerOut::Powered => 0,
+        PowerOut::LightsOut => 1,
+        PowerOut::Eyes => 2,
+        PowerOut::Jingle(_) => 3,
+        PowerOut::Darkness(_) => 4,
+        PowerOut::Attack => 5,
+    };
+    let stages: Vec<_> = seen.iter().map(stage).collect();
+    assert!(stages.windows(2).all(|pair| pair[0] <= pair[1]), "{seen:?}");
+    assert_eq!(stages.first(), Some(&0));
+    assert_eq!(stages.last(), Some(&5));
+}
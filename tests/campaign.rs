@@ -0,0 +1,127 @@
+use fnaf::{
+    Campaign, Command, Ending, Layout, Night, NightRecord, RampStep, Replay, Roster, Side,
+    TickOutcome,
+};
+
+mod common;
+use common::roster;
+
+fn levels(campaign: &Campaign, night: u8, roster: &Roster) -> Vec<u8> {
+    campaign
+        .animatronics(night, roster)
+        .iter()
+        .map(|anim| anim.difficulty)
+        .collect()
+}
+
+#[test]
+fn the_default_campaign_only_gets_harder() {
+    let roster = roster();
+    let campaign = Campaign::default_for(&roster);
+    assert_eq!(campaign.len(), 5);
+    assert_eq!(levels(&campaign, 1, &roster), vec![5, 3, 3, 2]);
+
+    for night in 2..=campaign.len() {
+        let before = levels(&campaign, night - 1, &roster);
+        let after = levels(&campaign, night, &roster);
+        assert!(before.iter().zip(after.iter()).all(|(a, b)| a <= b));
+    }
+}
+
+#[test]
+fn nights_past_the_end_replay_the_last_one() {
+    let roster = roster();
+    let campaign = Campaign::parse(
+        "[[nights]]\nlevels = { Freddy = 1 }\n\n[[nights]]\nlevels = { Foxy = 20 }\n",
+        &roster,
+    )
+    .unwrap();
+
+    assert_eq!(levels(&campaign, 1, &roster), vec![1, 0, 0, 0]);
+    assert_eq!(levels(&campaign, 9, &roster), vec![0, 0, 0, 20]);
+}
+
+#[test]
+fn bad_campaigns_are_rejected() {
+    let roster = roster();
+
+    assert!(Campaign::parse("nights = []\n", &roster).is_err());
+
+    let too_high = Campaign::parse("[[nights]]\nlevels = { Chica = 25 }\n", &roster);
+    assert!(too_high.unwrap_err().contains("night 1"));
+
+    let unknown = Campaign::parse("[[nights]]\nlevels = { Mangle = 3 }\n", &roster);
+    assert!(unknown.unwrap_err().contains("Mangle"));
+}
+
+#[test]
+fn a_night_ramp_replaces_the_campaign_one() {
+    let roster = roster();
+    let campaign = Campaign::default_for(&roster);
+
+    assert!(campaign.ramp(1).is_empty());
+    assert_eq!(campaign.ramp(2), campaign.default_ramp());
+    assert_eq!(
+        campaign.ramp(2).first(),
+        Some(&RampStep {
+            hour: 2,
+            name: String::from("Bonnie"),
+            gain: 1
+        })
+    );
+
+    let at_dawn = Campaign::parse(
+        "ramp = [{ hour = 6, levels = { Foxy = 1 } }]\n\n[[nights]]\nlevels = {}\n",
+        &roster,
+    );
+    assert!(at_dawn.is_err());
+}
+
+#[test]
+fn levels_go_up_on_the_hour_and_back_down_at_dusk() {
+    let layout = Layout::default();
+    let roster = roster();
+    let campaign = Campaign::default_for(&roster);
+    let mut night = Night::new(layout, campaign.animatronics(4, &roster), 3);
+    night.ramp = campaign.ramp(4);
+    // shut in, so nobody gets in before 4 AM
+    assert!(night.apply(Command::Close(Side::Left)));
+    assert!(night.apply(Command::Close(Side::Right)));
+
+    let bonnie = |night: &Night| night.map.anim_states[1].difficulty;
+    while night.clock.time < 4 * 60 {
+        assert_eq!(night.tick(), TickOutcome::Continue);
+    }
+    assert_eq!(bonnie(&night), 8 + 3);
+    assert_eq!(night.start_levels()[1], (String::from("Bonnie"), 8));
+
+    night.reset(3);
+    assert_eq!(bonnie(&night), 8);
+}
+
+#[test]
+fn replays_keep_the_ramp() {
+    let layout = Layout::default();
+    let roster = roster();
+    let campaign = Campaign::default_for(&roster);
+    let mut night = Night::new(layout.clone(), campaign.animatronics(5, &roster), 8);
+    night.ramp = campaign.ramp(5);
+
+    let outcome = loop {
+        night.apply(Command::Sit);
+        match night.tick() {
+            TickOutcome::Continue => {}
+            outcome => break outcome,
+        }
+    };
+    let ending = Ending::from_outcome(&outcome, night.clock);
+
+    let mut replay = Replay::new();
+    replay
+        .nights
+        .push(NightRecord::from_night(&night, ending.clone()));
+    let replay = Replay::parse(&replay.to_string()).unwrap();
+
+    assert_eq!(replay.nights[0].ramp, campaign.ramp(5));
+    assert_eq!(replay.nights[0].run(&layout, &roster).ok(), ending);
+}
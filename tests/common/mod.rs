@@ -0,0 +1,17 @@
+// helpers shared by the integration tests, not every test file uses all
+// of them
+#![allow(dead_code)]
+
+use fnaf::{Layout, Night, Roster};
+
+// the built in cast on the built in map
+pub fn roster() -> Roster {
+    Roster::default_for(&Layout::default()).unwrap()
+}
+
+// a night on the built in map with the whole built in cast
+pub fn night(seed: u64) -> Night {
+    let layout = Layout::default();
+    let anims = Roster::default_for(&layout).unwrap().animatronics();
+    Night::new(layout, anims, seed)
+}
@@ -0,0 +1,43 @@
+use fnaf::extras::animatronics_at;
+use fnaf::Extras;
+
+mod common;
+use common::roster;
+
+#[test]
+fn presets_set_levels_in_roster_order() {
+    let roster = roster();
+    let extras = Extras::default_for(&roster);
+
+    let preset = extras.find("4/20 MODE").unwrap();
+    let anims = animatronics_at(&preset.levels, &roster);
+    let names: Vec<_> = anims.iter().map(|anim| anim.name.as_str()).collect();
+    assert_eq!(names, vec!["Freddy", "Bonnie", "Chica", "Foxy"]);
+    assert!(anims.iter().all(|anim| anim.difficulty == 20));
+}
+
+#[test]
+fn characters_left_out_sit_at_zero() {
+    let roster = roster();
+    let extras = Extras::parse("[night_6]\nbonnie = 7\n", &roster).unwrap();
+
+    let levels: Vec<_> = animatronics_at(&extras.night_6, &roster)
+        .iter()
+        .map(|anim| anim.difficulty)
+        .collect();
+    assert_eq!(levels, vec![0, 7, 0, 0]);
+}
+
+#[test]
+fn bad_extras_are_rejected() {
+    let roster = roster();
+
+    let unknown = Extras::parse("[night_6]\nSpringtrap = 3\n", &roster);
+    assert!(unknown.unwrap_err().contains("Springtrap"));
+
+    let too_high = Extras::parse("[night_6]\nFreddy = 21\n", &roster);
+    assert!(too_high.is_err());
+
+    let twice = "[night_6]\n\n[[presets]]\nname = \"A\"\nlevels = {}\n\n[[presets]]\nname = \"a\"\nlevels = {}\n";
+    assert!(Extras::parse(twice, &roster).is_err());
+}
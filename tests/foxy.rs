@@ -0,0 +1,101 @@
+use fnaf::clock::TICK_RATE;
+use fnaf::power::{DRAIN_PER_BAR, UNIT};
+use fnaf::{Animatronic, Command, Layout, Locations, Night, Roster, Side, TickOutcome};
+
+mod common;
+use common::roster;
+
+fn foxy_night(level: u8, seed: u64) -> Night {
+    let profile = roster().find("Foxy").unwrap().clone();
+    Night::new(
+        Layout::default(),
+        vec![Animatronic::from_profile(profile, level)],
+        seed,
+    )
+}
+
+#[test]
+fn foxy_at_level_0_never_leaves_the_cove() {
+    for seed in 0..20 {
+        let mut night = foxy_night(0, seed);
+        let start = night.map.anim_states[0].location;
+
+        loop {
+            // the camera gives him his second roll
+            assert!(night.apply(Command::Camera));
+            let outcome = night.tick();
+            let foxy = &night.map.anim_states[0];
+            assert_eq!((foxy.location, foxy.stage), (start, 0));
+            if outcome != TickOutcome::Continue {
+                break;
+            }
+        }
+    }
+}
+
+// Foxy at the top level one stage before he runs
+fn about_to_run(seed: u64) -> Night {
+    let mut night = foxy_night(20, seed);
+    night.map.anim_states[0].stage = 2;
+
+    assert_eq!(night.tick(), TickOutcome::Continue);
+    let foxy = &night.map.anim_states[0];
+    assert_eq!(foxy.location, Locations::HallwayL);
+    assert!(foxy.sprinting);
+    night
+}
+
+#[test]
+fn the_curtain_opens_one_stage_at_a_time() {
+    let mut night = foxy_night(20, 1);
+
+    for stage in 1..3 {
+        assert_eq!(night.tick(), TickOutcome::Continue);
+        assert_eq!(night.map.anim_states[0].stage, stage);
+        assert_eq!(night.map.anim_states[0].location, Locations::PirateCove);
+    }
+}
+
+#[test]
+fn an_open_door_lets_him_in() {
+    let mut night = about_to_run(1);
+
+    assert_eq!(night.tick(), TickOutcome::Continue);
+    assert_eq!(
+        night.map.anim_states[0].location,
+        Locations::SecurityOfficeAttack
+    );
+    assert_eq!(night.tick(), TickOutcome::Killed(String::from("Foxy")));
+}
+
+#[test]
+fn a_closed_door_gets_banged_on_and_costs_power() {
+    let mut night = about_to_run(1);
+    assert!(night.apply(Command::Close(Side::Left)));
+    night.take_messages();
+    let before = night.battery.charge;
+
+    assert_eq!(night.tick(), TickOutcome::Continue);
+    let messages = night.take_messages();
+    assert!(messages.contains(&String::from("You hear banging on the left door!")));
+
+    // two bars for the turn and the bang on top
+    assert_eq!(
+        before - night.battery.charge,
+        2 * DRAIN_PER_BAR * TICK_RATE + 5 * UNIT
+    );
+
+    let foxy = &night.map.anim_states[0];
+    assert_eq!(foxy.location, Locations::PirateCove);
+    assert!(!foxy.sprinting);
+}
+
+#[test]
+fn a_bang_can_cost_at_most_the_whole_battery() {
+    let layout = Layout::default();
+    let text = "[[characters]]\nname = \"Foxy\"\nglyph = \"X\"\nstart = \"PirateCove\"\ndifficulty = 2\ntells = [[\"Footsteps\", 1]]\n\n[characters.cove]\nstages = 3\ndoor = \"left\"\nbang_power = 200\n";
+
+    let err = Roster::parse(text, &layout).unwrap_err();
+    assert!(err.contains("bang_power"), "{err}");
+    assert!(Roster::parse(&text.replace("200", "100"), &layout).is_ok());
+}
@@ -0,0 +1,94 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use fnaf::commands::CameraId;
+use fnaf::golden_freddy::NAME;
+use fnaf::{
+    Animatronic, Command, GoldenFreddy, Hallucination, Layout, Locations, Night, TickOutcome,
+};
+
+mod common;
+
+// an empty building with him waiting on the west hall camera
+fn haunted() -> Night {
+    let mut night = Night::new(Layout::default(), Vec::new(), 1);
+    night.golden_freddy.state = Hallucination::Armed;
+    night
+}
+
+fn west_hall() -> Command {
+    Command::View(CameraId::parse("2A").unwrap())
+}
+
+#[test]
+fn only_his_room_sets_him_off() {
+    let mut golden = GoldenFreddy::new(Some(Locations::HallwayL));
+    golden.state = Hallucination::Armed;
+
+    assert_eq!(golden.camera_viewed(&[Locations::ShowStage]), None);
+    assert_eq!(golden.state, Hallucination::Armed);
+    golden.camera_viewed(&[Locations::HallwayL]);
+    assert_eq!(golden.state, Hallucination::Seen);
+}
+
+#[test]
+fn never_without_a_room() {
+    let mut golden = GoldenFreddy::new(None);
+    let mut rng = StdRng::seed_from_u64(1);
+    for _ in 0..200 {
+        golden.roll(&mut rng);
+        assert_eq!(golden.state, Hallucination::Dormant);
+    }
+}
+
+#[test]
+fn sitting_with_him_in_the_office_kills() {
+    let mut night = haunted();
+
+    assert!(night.apply(west_hall()));
+    let view = night.take_camera_views().pop().unwrap();
+    assert!(view.contains("[g]"), "{view}");
+
+    assert_eq!(night.tick(), TickOutcome::Continue);
+    assert_eq!(night.golden_freddy.state, Hallucination::InOffice);
+    assert!(night.apply(Command::Sit));
+    assert_eq!(night.tick(), TickOutcome::Killed(String::from(NAME)));
+    assert!(night.death_message().contains("was never really there"));
+}
+
+#[test]
+fn the_camera_makes_him_go_away() {
+    let mut night = haunted();
+    assert!(night.apply(west_hall()));
+    assert_eq!(night.tick(), TickOutcome::Continue);
+    night.take_messages();
+
+    assert!(night.apply(Command::Camera));
+    assert_eq!(
+        night.take_messages(),
+        vec!["When you look up from the camera the office is empty again."]
+    );
+    assert_eq!(night.golden_freddy.state, Hallucination::Dormant);
+    assert_eq!(night.tick(), TickOutcome::Continue);
+}
+
+#[test]
+fn a_real_killer_gets_the_blame() {
+    let profile = common::roster().find("Bonnie").unwrap().clone();
+    let mut night = Night::new(
+        Layout::default(),
+        vec![Animatronic::from_profile(profile, 20)],
+        1,
+    );
+    night.map.anim_states[0].location = Locations::SecurityOfficeStaticL;
+    assert_eq!(night.tick(), TickOutcome::Continue);
+
+    // he shows up in the office on the very tick Bonnie gets in
+    night.golden_freddy.state = Hallucination::Seen;
+    assert_eq!(night.tick(), TickOutcome::Killed(String::from("Bonnie")));
+    assert_eq!(night.golden_freddy.state, Hallucination::InOffice);
+    assert_eq!(
+        night.death_message(),
+        "You were attacked by Bonnie! Game over!"
+    );
+}
@@ -0,0 +1,104 @@
+use fnaf::{Layout, Locations};
+
+// the smallest map that passes: a stage, one hallway and one door
+const SMALL: &str = r#"
+name = "Small"
+spawn = "ShowStage"
+attack_room = "SecurityOfficeAttack"
+template = ""
+
+[[rooms]]
+id = "ShowStage"
+label = "ss"
+exits = ["HallwayL"]
+
+[[rooms]]
+id = "HallwayL"
+label = "hl"
+exits = ["ShowStage", "SecurityOfficeStaticL"]
+one_way = ["SecurityOfficeStaticL"]
+
+[[rooms]]
+id = "SecurityOfficeStaticL"
+label = "sosl"
+exits = ["SecurityOfficeAttack"]
+one_way = ["SecurityOfficeAttack"]
+
+[[rooms]]
+id = "SecurityOfficeAttack"
+label = "soa"
+exits = ["SecurityOfficeAttack"]
+
+[[doors]]
+side = "left"
+hallway = "HallwayL"
+room = "SecurityOfficeStaticL"
+"#;
+
+fn error(text: &str) -> String {
+    Layout::parse(text).unwrap_err()
+}
+
+#[test]
+fn the_maps_that_ship_are_valid() {
+    let layout = Layout::parse(SMALL).unwrap();
+    assert_eq!(
+        layout.distance(Locations::ShowStage, Locations::SecurityOfficeAttack),
+        Some(3)
+    );
+    Layout::default();
+}
+
+#[test]
+fn every_exit_needs_a_way_back_unless_it_is_one_way() {
+    let text = SMALL.replace(
+        "exits = [\"ShowStage\", \"SecurityOfficeStaticL\"]\none_way = [\"SecurityOfficeStaticL\"]",
+        "exits = [\"ShowStage\", \"SecurityOfficeStaticL\"]",
+    );
+    assert!(error(&text).contains("leads to SecurityOfficeStaticL but not back"));
+}
+
+#[test]
+fn every_room_has_to_be_reachable() {
+    let text =
+        format!("{SMALL}\n[[rooms]]\nid = \"Kitchen\"\nlabel = \"k\"\nexits = [\"Kitchen\"]\n");
+    assert!(error(&text).contains("Kitchen can't be reached"));
+
+    // unless something starts in it
+    let text = text.replace(
+        "template = \"\"",
+        "hideouts = [\"Kitchen\"]\ntemplate = \"\"",
+    );
+    assert!(Layout::parse(&text).is_ok());
+}
+
+#[test]
+fn the_attack_room_has_to_exist() {
+    let text = SMALL.replace(
+        "attack_room = \"SecurityOfficeAttack\"",
+        "attack_room = \"Kitchen\"",
+    );
+    assert!(error(&text).contains("attack room Kitchen is not a room"));
+}
+
+#[test]
+fn doors_sit_between_a_hallway_and_the_attack_room() {
+    let text = SMALL.replace("hallway = \"HallwayL\"", "hallway = \"ShowStage\"");
+    assert!(error(&text).contains("ShowStage does not lead to SecurityOfficeStaticL"));
+
+    let text = SMALL
+        .replace("hallway = \"HallwayL\"", "hallway = \"ShowStage\"")
+        .replace("room = \"SecurityOfficeStaticL\"", "room = \"HallwayL\"");
+    assert!(error(&text).contains("HallwayL does not lead to the attack room"));
+
+    let text = format!(
+        "{SMALL}\n[[doors]]\nside = \"left\"\nhallway = \"HallwayL\"\nroom = \"SecurityOfficeStaticL\"\n"
+    );
+    assert!(error(&text).contains("more than one left door"));
+}
+
+#[test]
+fn room_ids_come_from_locations() {
+    let text = SMALL.replace("id = \"ShowStage\"", "id = \"BackStage\"");
+    assert!(error(&text).contains("unknown variant `BackStage`"));
+}
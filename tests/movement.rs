@@ -0,0 +1,72 @@
+use fnaf::clock::TICK_RATE;
+use fnaf::{Animatronic, Command, Layout, Night, Roster, TickOutcome};
+
+mod common;
+use common::roster;
+
+#[test]
+fn chances_to_move_follow_each_rhythm() {
+    let roster = roster();
+    let freddy = roster.find("Freddy").unwrap().clone();
+    let bonnie = roster.find("Bonnie").unwrap().clone();
+    let mut freddy = Animatronic::from_profile(freddy, 5);
+    let mut bonnie = Animatronic::from_profile(bonnie, 5);
+
+    // every 9 minutes against one turn of 15
+    let freddy_chances: Vec<u32> = (0..4).map(|_| freddy.opportunities(TICK_RATE)).collect();
+    assert_eq!(freddy_chances, vec![1, 2, 2, 1]);
+
+    let bonnie_chances: Vec<u32> = (0..4).map(|_| bonnie.opportunities(TICK_RATE)).collect();
+    assert_eq!(bonnie_chances, vec![1, 1, 1, 1]);
+}
+
+#[test]
+fn a_slow_character_only_moves_on_the_hour() {
+    let layout = Layout::default();
+    let mut profile = roster().find("Bonnie").unwrap().clone();
+    profile.move_every = 60;
+    let start = profile.start;
+    let mut night = Night::new(layout, vec![Animatronic::from_profile(profile, 20)], 2);
+
+    for _ in 0..3 {
+        assert!(night.apply(Command::Sit));
+        assert_eq!(night.tick(), TickOutcome::Continue);
+        assert_eq!(night.map.anim_states[0].location, start);
+    }
+
+    assert!(night.apply(Command::Sit));
+    assert_eq!(night.tick(), TickOutcome::Continue);
+    assert_ne!(night.map.anim_states[0].location, start);
+}
+
+#[test]
+fn a_character_has_to_get_a_chance_at_some_point() {
+    let layout = Layout::default();
+    let text = "[[characters]]\nname = \"Bonnie\"\nglyph = \"B\"\nstart = \"ShowStage\"\ndifficulty = 3\ntells = [[\"Noise\", 1]]\nmove_every = 0\n";
+
+    let err = Roster::parse(text, &layout).unwrap_err();
+    assert!(err.contains("move_every"));
+}
+
+#[test]
+fn a_level_0_character_never_leaves_its_start() {
+    for seed in 0..20 {
+        let profile = roster().find("Bonnie").unwrap().clone();
+        let start = profile.start;
+        let mut night = Night::new(
+            Layout::default(),
+            vec![Animatronic::from_profile(profile, 0)],
+            seed,
+        );
+
+        loop {
+            assert!(night.apply(Command::Sit));
+            let outcome = night.tick();
+            assert_eq!(night.map.anim_states[0].location, start);
+            if outcome != TickOutcome::Continue {
+                assert_eq!(outcome, TickOutcome::Survived);
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use fnaf::clock::{END_TIME, TICK_RATE};
+use fnaf::{Animatronic, Command, Layout, Locations, Night, Side, TickOutcome};
+
+mod common;
+use common::roster;
+
+// Bonnie alone, as sure to move as he gets
+fn bonnie_at(location: Locations) -> Night {
+    let profile = roster().find("Bonnie").unwrap().clone();
+    let mut night = Night::new(
+        Layout::default(),
+        vec![Animatronic::from_profile(profile, 20)],
+        1,
+    );
+    night.map.anim_states[0].location = location;
+    night
+}
+
+#[test]
+fn doors_and_lights_follow_the_commands() {
+    let mut night = Night::new(Layout::default(), Vec::new(), 1);
+
+    assert!(night.apply(Command::Door(Side::Left)));
+    assert!(night.door_closed(Side::Left));
+    assert_eq!(night.battery.usage(), 2);
+    assert!(night.apply(Command::Door(Side::Left)));
+    assert!(!night.door_closed(Side::Left));
+
+    // the explicit ones never flip back
+    assert!(night.apply(Command::Close(Side::Right)));
+    assert!(night.apply(Command::Close(Side::Right)));
+    assert!(night.door_closed(Side::Right));
+    assert!(night.apply(Command::LightOn(Side::Left)));
+    assert!(night.apply(Command::LightOn(Side::Left)));
+    assert!(night.light_on(Side::Left));
+    assert_eq!(night.battery.usage(), 3);
+
+    assert_eq!(night.inputs.len(), 6);
+}
+
+#[test]
+fn rejected_commands_say_why_and_are_not_recorded() {
+    let mut night = Night::new(Layout::default(), Vec::new(), 1);
+    night.take_messages();
+
+    assert!(!night.enter("cam 9"));
+    assert_eq!(night.take_messages(), vec!["There is no camera 9."]);
+
+    night.battery.shutdown();
+    assert!(!night.apply(Command::Close(Side::Left)));
+    assert!(night.take_messages()[0].contains("the power is out"));
+    assert!(night.apply(Command::Sit));
+
+    assert_eq!(night.inputs, vec![(0, Command::Sit)]);
+}
+
+#[test]
+fn an_empty_building_is_survived_at_dawn() {
+    let mut night = Night::new(Layout::default(), Vec::new(), 1);
+
+    for _ in 1..END_TIME / TICK_RATE {
+        assert_eq!(night.tick(), TickOutcome::Continue);
+    }
+    assert_eq!(night.tick(), TickOutcome::Survived);
+    assert_eq!(night.clock.time, END_TIME);
+    assert_eq!(night.ticks, END_TIME / TICK_RATE);
+}
+
+#[test]
+fn walking_into_the_office_kills() {
+    let mut night = bonnie_at(Locations::SecurityOfficeStaticL);
+
+    assert_eq!(night.tick(), TickOutcome::Continue);
+    assert_eq!(
+        night.map.anim_states[0].location,
+        Locations::SecurityOfficeAttack
+    );
+    assert_eq!(night.tick(), TickOutcome::Killed(String::from("Bonnie")));
+    assert!(night.death_message().contains("Bonnie"));
+}
+
+#[test]
+fn a_closed_door_sends_them_back_to_the_hallway() {
+    let mut night = bonnie_at(Locations::SecurityOfficeStaticL);
+    assert!(night.apply(Command::Close(Side::Left)));
+
+    assert_eq!(night.tick(), TickOutcome::Continue);
+    assert_ne!(
+        night.map.anim_states[0].location,
+        Locations::SecurityOfficeAttack
+    );
+    assert_eq!(night.tick(), TickOutcome::Continue);
+}
+
+#[test]
+fn the_same_seed_plays_the_same_night() {
+    let play = |seed: u64| {
+        let mut night = common::night(seed);
+        let mut outcome = TickOutcome::Continue;
+        while outcome == TickOutcome::Continue {
+            night.apply(Command::Sit);
+            outcome = night.tick();
+        }
+        (outcome, night.clock.time)
+    };
+
+    for seed in 0..5 {
+        assert_eq!(play(seed), play(seed));
+    }
+}
@@ -0,0 +1,104 @@
+use fnaf::clock::{END_TIME, TICK_RATE};
+use fnaf::{Battery, Command, Layout, Night, PowerDraw, Side, TickOutcome};
+
+const TICKS_PER_NIGHT: u32 = END_TIME / TICK_RATE;
+
+fn run_ticks(battery: &mut Battery, ticks: u32) {
+    for _ in 0..ticks {
+        battery.update_power(TICK_RATE);
+    }
+}
+
+#[test]
+fn idle_night_costs_a_third_of_the_battery() {
+    let mut battery = Battery::new();
+    run_ticks(&mut battery, TICKS_PER_NIGHT);
+
+    assert_eq!(battery.usage(), 1);
+    assert_eq!(battery.percent(), 64);
+}
+
+#[test]
+fn drain_follows_usage_bars() {
+    let mut battery = Battery::new();
+    battery.add_power_draw(PowerDraw::Light(Side::Left));
+    run_ticks(&mut battery, 4);
+
+    // two bars for an hour: 2 * 0.1% * 60 minutes
+    assert_eq!(battery.usage(), 2);
+    assert_eq!(battery.percent(), 88);
+}
+
+#[test]
+fn both_doors_count_separately() {
+    let mut battery = Battery::new();
+    battery.add_power_draw(PowerDraw::Door(Side::Left));
+    battery.add_power_draw(PowerDraw::Door(Side::Right));
+    assert_eq!(battery.usage(), 3);
+
+    battery.remove_power_draw(PowerDraw::Door(Side::Left));
+    assert_eq!(battery.usage(), 2);
+}
+
+#[test]
+fn usage_tops_out_at_four_bars() {
+    let mut battery = Battery::new();
+    battery.add_power_draw(PowerDraw::Door(Side::Left));
+    battery.add_power_draw(PowerDraw::Door(Side::Right));
+    battery.add_power_draw(PowerDraw::Light(Side::Left));
+    battery.add_power_draw(PowerDraw::Camera);
+    battery.add_power_draw(PowerDraw::Camera);
+
+    assert_eq!(battery.usage(), 4);
+}
+
+#[test]
+fn battery_never_goes_below_zero() {
+    let mut battery = Battery::new();
+    battery.add_power_draw(PowerDraw::Door(Side::Left));
+    battery.add_power_draw(PowerDraw::Door(Side::Right));
+    run_ticks(&mut battery, TICKS_PER_NIGHT);
+    assert!(battery.is_empty());
+    assert_eq!(battery.percent(), 0);
+
+    battery.shutdown();
+    assert_eq!(battery.percent(), 0);
+    assert_eq!(battery.usage(), 0);
+}
+
+#[test]
+fn sitting_through_a_night_leaves_the_expected_power() {
+    let mut night = Night::new(Layout::default(), Vec::new(), 1);
+
+    let outcome = loop {
+        assert!(night.apply(Command::Sit));
+        match night.tick() {
+            TickOutcome::Continue => {}
+            outcome => break outcome,
+        }
+    };
+
+    assert_eq!(outcome, TickOutcome::Survived);
+    assert_eq!(night.battery.percent(), 64);
+}
+
+#[test]
+fn both_doors_shut_all_night_runs_the_power_out() {
+    let mut night = Night::new(Layout::default(), Vec::new(), 1);
+    assert!(night.apply(Command::Close(Side::Left)));
+    assert!(night.apply(Command::Close(Side::Right)));
+
+    // three bars: 4.5% a tick, empty after 23 ticks, just before 6 AM
+    for _ in 0..22 {
+        assert!(night.apply(Command::Sit));
+        assert_eq!(night.tick(), TickOutcome::Continue);
+    }
+    assert!(night.battery.is_online);
+    assert_eq!(night.battery.percent(), 1);
+
+    assert!(night.apply(Command::Sit));
+    night.tick();
+    assert!(!night.battery.is_online);
+    assert_eq!(night.battery.percent(), 0);
+    assert!(!night.door_closed(Side::Left));
+}
@@ -0,0 +1,80 @@
+use fnaf::commands::CameraId;
+use fnaf::{Animatronic, Command, Locations, Map, Tells};
+
+mod common;
+use common::roster;
+
+fn map_with(anims: Vec<Animatronic>) -> Map {
+    let mut map = Map::new();
+    map.anim_states = anims;
+    map
+}
+
+fn at(name: &str, location: Locations, tell: Tells) -> Animatronic {
+    let profile = roster().find(name).unwrap().clone();
+    let mut anim = Animatronic::from_profile(profile, 0);
+    anim.location = location;
+    anim.current_tell = tell;
+    anim
+}
+
+#[test]
+fn crowded_show_stage_shows_everyone() {
+    let map = map_with(roster().animatronics());
+
+    let rendered = map.render_map();
+    assert!(rendered.contains("[0]     [FBC]"), "{rendered}");
+}
+
+#[test]
+fn occupants_are_drawn_in_roster_order() {
+    let map = map_with(vec![
+        at("Chica", Locations::DiningAreaC, Tells::Visual),
+        at("Freddy", Locations::DiningAreaC, Tells::Laughing),
+        at("Bonnie", Locations::DiningAreaC, Tells::Static),
+    ]);
+
+    let rendered = map.render_map();
+    assert!(rendered.contains("--Cls--"), "{rendered}");
+    assert_eq!(rendered, map.render_map());
+}
+
+#[test]
+fn camera_shows_every_occupant_of_its_room_only() {
+    let map = map_with(vec![
+        at("Freddy", Locations::HallwayL, Tells::Visual),
+        at("Bonnie", Locations::HallwayL, Tells::Footsteps),
+        at("Chica", Locations::Kitchen, Tells::Visual),
+    ]);
+
+    let view = map.render_camera(Locations::HallwayL);
+    assert!(view.contains("[Ff]"), "{view}");
+    assert!(!view.contains("[C]"), "{view}");
+}
+
+#[test]
+fn audio_only_camera_only_carries_sound() {
+    let map = map_with(vec![
+        at("Bonnie", Locations::Kitchen, Tells::Visual),
+        at("Chica", Locations::Kitchen, Tells::Noise),
+    ]);
+
+    let view = map.render_camera(Locations::Kitchen);
+    assert!(view.contains("[n]"), "{view}");
+    assert!(!view.contains('B'), "{view}");
+}
+
+#[test]
+fn the_hall_corners_have_cameras() {
+    let mut night = common::night(1);
+    night.map.anim_states[1].location = Locations::SecurityOfficeStaticL;
+
+    assert!(night.apply(Command::View(CameraId::parse("2b").unwrap())));
+    let view = night.take_camera_views().pop().unwrap();
+    assert!(view.starts_with("CAM 2B - West Hall Corner"), "{view}");
+    assert!(view.contains("[B-- "), "{view}");
+
+    assert!(night.apply(Command::View(CameraId::parse("4B").unwrap())));
+    let view = night.take_camera_views().pop().unwrap();
+    assert!(view.starts_with("CAM 4B - East Hall Corner"), "{view}");
+}
@@ -0,0 +1,146 @@
+use fnaf::{
+    AiMode, Command, Ending, Layout, Night, NightRecord, RampStep, Replay, Roster, Side,
+    TickOutcome,
+};
+
+mod common;
+use common::{night, roster};
+
+// a whole night with a few inputs, recorded the way --record does it
+fn recorded(seed: u64) -> NightRecord {
+    let mut night = night(seed);
+    let turns = [
+        Command::Close(Side::Left),
+        Command::Camera,
+        Command::Open(Side::Left),
+        Command::LightOn(Side::Right),
+        Command::Sit,
+    ];
+
+    let mut turn = 0;
+    let outcome = loop {
+        night.apply(turns[turn % turns.len()]);
+        turn += 1;
+        match night.tick() {
+            TickOutcome::Continue => {}
+            outcome => break outcome,
+        }
+    };
+
+    NightRecord::from_night(&night, Ending::from_outcome(&outcome, night.clock))
+}
+
+#[test]
+fn a_replay_reads_back_the_way_it_was_written() {
+    let mut replay = Replay::new();
+    replay.nights.push(recorded(1));
+    replay.nights.push(recorded(2));
+
+    let text = replay.to_string();
+    assert!(text.starts_with("fnafterminal-replay 1\nversion "));
+    assert_eq!(Replay::parse(&text), Ok(replay));
+}
+
+#[test]
+fn playing_it_back_ends_the_same_way() {
+    let layout = Layout::default();
+    let roster = roster();
+
+    for seed in 0..10 {
+        let record = recorded(seed);
+        assert!(!record.inputs.is_empty());
+        assert_eq!(record.run(&layout, &roster).ok(), record.ending);
+    }
+}
+
+#[test]
+fn inputs_after_the_end_do_not_verify() {
+    let mut record = recorded(3);
+    let last = record.inputs.last().unwrap().0;
+    record.inputs.push((last + 100, Command::Sit));
+
+    let err = record.run(&Layout::default(), &roster()).unwrap_err();
+    assert!(err.contains("still unplayed"), "{err}");
+}
+
+#[test]
+fn old_recordings_without_an_ai_are_classic() {
+    let text = "fnafterminal-replay 1\nversion 0.1.0\nnight seed 7 difficulties Freddy=1 Bonnie=2\n0 left door\nend survived\n";
+    let replay = Replay::parse(text).unwrap();
+
+    let night = &replay.nights[0];
+    assert_eq!(night.ai, AiMode::Classic);
+    assert_eq!(night.seed, 7);
+    assert_eq!(night.difficulties[1], (String::from("Bonnie"), 2));
+    assert_eq!(night.inputs, vec![(0, Command::Door(Side::Left))]);
+    assert_eq!(night.ending, Some(Ending::Survived));
+}
+
+#[test]
+fn broken_files_are_refused() {
+    assert!(Replay::parse("version 0.1.0\n").is_err());
+    assert!(Replay::parse("fnafterminal-replay 1\nnight seed 1 difficulties\n").is_err());
+
+    let err = Replay::parse("fnafterminal-replay 1\nversion 0.1.0\nend\n").unwrap_err();
+    assert!(err.contains("end outside of a night"));
+
+    let err = Replay::parse(
+        "fnafterminal-replay 1\nversion 0.1.0\nnight seed 1 difficulties\n0 dance\nend\n",
+    )
+    .unwrap_err();
+    assert!(err.contains("line 4"), "{err}");
+
+    let err =
+        Replay::parse("fnafterminal-replay 1\nversion 0.1.0\nnight seed 1 difficulties\n0 sit\n")
+            .unwrap_err();
+    assert!(err.contains("no end"));
+}
+
+#[test]
+fn names_with_spaces_survive_the_round_trip() {
+    let layout = Layout::default();
+    let roster = Roster::parse(
+        "[[characters]]\nname = \"Toy Bonnie\"\nglyph = \"T\"\nstart = \"ShowStage\"\ndifficulty = 3\ntells = [[\"Noise\", 1]]\n",
+        &layout,
+    )
+    .unwrap();
+
+    let mut night = Night::new(layout.clone(), roster.animatronics(), 5);
+    night.ramp = vec![RampStep::parse("2:\"Toy Bonnie\"+4").unwrap()];
+    let outcome = loop {
+        night.apply(Command::Sit);
+        match night.tick() {
+            TickOutcome::Continue => {}
+            outcome => break outcome,
+        }
+    };
+
+    let mut replay = Replay::new();
+    replay.nights.push(NightRecord::from_night(
+        &night,
+        Ending::from_outcome(&outcome, night.clock),
+    ));
+    let text = replay.to_string();
+    assert!(
+        text.contains("\"Toy Bonnie\"=3 ramp 2:\"Toy Bonnie\"+4"),
+        "{text}"
+    );
+
+    let parsed = Replay::parse(&text).unwrap();
+    assert_eq!(parsed, replay);
+    assert_eq!(parsed.nights[0].difficulties[0].0, "Toy Bonnie");
+    assert_eq!(
+        parsed.nights[0].run(&layout, &roster).ok(),
+        replay.nights[0].ending
+    );
+}
+
+#[test]
+fn names_with_quotes_are_refused() {
+    let err = Roster::parse(
+        "[[characters]]\nname = \"Toy \\\"B\\\"\"\nglyph = \"T\"\nstart = \"ShowStage\"\ndifficulty = 3\ntells = [[\"Noise\", 1]]\n",
+        &Layout::default(),
+    )
+    .unwrap_err();
+    assert!(err.contains("can't be a name"), "{err}");
+}
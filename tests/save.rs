@@ -0,0 +1,154 @@
+use std::path::PathBuf;
+
+use fnaf::extras::{animatronics_at, Levels};
+use fnaf::{Campaign, Command, Layout, Night, Save, Saves, Side, TickOutcome};
+
+mod common;
+use common::roster;
+
+// a fresh save directory per test so they can run side by side
+fn saves_in(name: &str) -> Saves {
+    let dir: PathBuf =
+        std::env::temp_dir().join(format!("fnafterminal-test-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    Saves::new(dir)
+}
+
+#[test]
+fn a_save_comes_back_the_way_it_was_stored() {
+    let saves = saves_in("round-trip");
+
+    let mut save = Save::default();
+    // bigger than any toml integer
+    save.start_night(3, u64::MAX);
+    save.complete_night(2, 5);
+    saves.store("default", &save).unwrap();
+
+    let loaded = saves.load("default").unwrap();
+    assert_eq!(loaded.night, 3);
+    assert_eq!(loaded.nights_completed, 2);
+    assert_eq!(loaded.seed, u64::MAX);
+
+    std::fs::remove_dir_all(&saves.dir).unwrap();
+}
+
+#[test]
+fn slots_are_listed_and_deleted() {
+    let saves = saves_in("slots");
+    assert!(saves.slots().is_empty());
+    assert!(!saves.load("never-saved").unwrap().in_progress());
+
+    saves.store("b", &Save::default()).unwrap();
+    saves.store("a", &Save::default()).unwrap();
+    assert_eq!(saves.slots(), vec!["a", "b"]);
+
+    saves.delete("a").unwrap();
+    assert_eq!(saves.slots(), vec!["b"]);
+    assert!(saves.store("../escape", &Save::default()).is_err());
+
+    std::fs::remove_dir_all(&saves.dir).unwrap();
+}
+
+#[test]
+fn beating_the_last_night_unlocks_custom_night() {
+    let mut save = Save::default();
+    save.start_night(5, 1);
+
+    save.complete_night(5, 5);
+    assert!(!save.in_progress());
+    assert_eq!(save.nights_completed, 5);
+    assert_eq!(save.stars(), 1);
+    assert!(save.custom_night);
+}
+
+#[test]
+fn stars_come_from_night_six_and_a_maxed_custom_night() {
+    let mut save = Save::default();
+    save.complete_night(5, 5);
+    assert!(save.night_six);
+
+    save.complete_night_six();
+    assert_eq!(save.stars(), 2);
+
+    let levels = |level: u8| -> Vec<(String, u8)> {
+        roster()
+            .characters
+            .iter()
+            .map(|profile| (profile.name.clone(), level))
+            .collect()
+    };
+    save.complete_custom_night(&levels(19));
+    assert_eq!(save.stars(), 2);
+
+    save.complete_custom_night(&levels(20));
+    assert_eq!(save.stars(), 3);
+}
+
+fn maxed_levels() -> Vec<(String, u8)> {
+    roster()
+        .characters
+        .iter()
+        .map(|profile| (profile.name.clone(), 20))
+        .collect()
+}
+
+#[test]
+fn a_maxed_custom_night_first_is_not_night_six() {
+    let saves = saves_in("out-of-order");
+    let mut save = Save::default();
+    save.complete_night(5, 5);
+    save.complete_custom_night(&maxed_levels());
+    saves.store("default", &save).unwrap();
+
+    let save = saves.load("default").unwrap();
+    assert_eq!(save.stars(), 2);
+    assert!(save.five_nights && save.max_custom);
+    assert!(!save.night_six_beaten);
+
+    std::fs::remove_dir_all(&saves.dir).unwrap();
+}
+
+#[test]
+fn old_star_counts_still_load() {
+    let saves = saves_in("old-stars");
+    std::fs::create_dir_all(&saves.dir).unwrap();
+    std::fs::write(saves.dir.join("old.toml"), "seed = \"1\"\nstars = 2\n").unwrap();
+
+    let save = saves.load("old").unwrap();
+    assert!(save.five_nights && save.night_six_beaten);
+    assert!(!save.max_custom);
+
+    saves.store("old", &save).unwrap();
+    let text = std::fs::read_to_string(saves.dir.join("old.toml")).unwrap();
+    assert!(!text.contains("stars"), "{text}");
+
+    std::fs::remove_dir_all(&saves.dir).unwrap();
+}
+
+#[test]
+fn ramping_up_to_20_is_not_starting_at_20() {
+    let roster = roster();
+    let campaign = Campaign::default_for(&roster);
+    let levels: Levels = [("Freddy", 20), ("Bonnie", 17), ("Chica", 18), ("Foxy", 18)]
+        .into_iter()
+        .map(|(name, level)| (String::from(name), level))
+        .collect();
+
+    let mut night = Night::new(Layout::default(), animatronics_at(&levels, &roster), 4);
+    night.ramp = campaign.default_ramp();
+    // shut in, so nobody gets in before 4 AM
+    assert!(night.apply(Command::Close(Side::Left)));
+    assert!(night.apply(Command::Close(Side::Right)));
+    while night.clock.time < 4 * 60 {
+        assert_eq!(night.tick(), TickOutcome::Continue);
+    }
+    assert!(night
+        .map
+        .anim_states
+        .iter()
+        .all(|anim| anim.difficulty == 20));
+
+    let mut save = Save::default();
+    save.complete_custom_night(&night.start_levels());
+    assert_eq!(save.stars(), 0);
+}
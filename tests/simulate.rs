@@ -0,0 +1,48 @@
+use fnaf::simulate::simulate;
+use fnaf::{AiMode, Bot, Layout, Summary};
+
+mod common;
+use common::roster;
+
+fn run(levels: &[u8], bot: Bot, seed: u64) -> Summary {
+    simulate(
+        &Layout::default(),
+        &roster(),
+        levels,
+        AiMode::Hunt,
+        bot,
+        200,
+        seed,
+    )
+}
+
+#[test]
+fn every_night_is_counted_once() {
+    let summary = run(&[5, 3, 3, 2], Bot::Sit, 11);
+    let deaths: u32 = summary.deaths_by_hour.iter().sum();
+    let kills: u32 = summary.killers.iter().map(|(_, kills)| kills).sum();
+
+    assert_eq!(summary.nights, 200);
+    assert_eq!(summary.survived + deaths, 200);
+    assert_eq!(kills, deaths);
+    assert_eq!(summary.power_left.len() as u32, summary.survived);
+}
+
+#[test]
+fn same_seed_same_numbers() {
+    let first = run(&[10, 10, 10, 10], Bot::Sit, 5);
+    let second = run(&[10, 10, 10, 10], Bot::Sit, 5);
+
+    assert_eq!(first.survived, second.survived);
+    assert_eq!(first.deaths_by_hour, second.deaths_by_hour);
+    assert_eq!(first.killers, second.killers);
+}
+
+#[test]
+fn top_levels_survive_less_than_zero_levels() {
+    let easy = run(&[0, 0, 0, 0], Bot::Sit, 3);
+    let hard = run(&[20, 20, 20, 20], Bot::Sit, 3);
+
+    assert!(easy.survival_rate() > hard.survival_rate());
+    assert_eq!(easy.levels[3], (String::from("Foxy"), 0));
+}
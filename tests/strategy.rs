@@ -0,0 +1,59 @@
+use fnaf::simulate::simulate;
+use fnaf::{
+    AiMode, Bot, CloseOnBreathing, Command, Layout, Locations, Night, Observation, Side, Strategy,
+};
+
+mod common;
+use common::{night, roster};
+
+fn put(night: &mut Night, name: &str, location: Locations) {
+    let anim = night
+        .map
+        .anim_states
+        .iter_mut()
+        .find(|anim| anim.name == name)
+        .unwrap();
+    anim.location = location;
+}
+
+#[test]
+fn the_hallway_is_only_seen_with_the_light_on() {
+    let mut night = night(1);
+    put(&mut night, "Bonnie", Locations::HallwayL);
+
+    let seen = Observation::new(&night, Vec::new(), None);
+    assert!(!seen.seen_at(Side::Left));
+
+    assert!(night.apply(Command::LightOn(Side::Left)));
+    let seen = Observation::new(&night, Vec::new(), None);
+    assert_eq!(seen.seen, vec![(Side::Left, String::from("Bonnie"))]);
+    assert!(!seen.breathing(Side::Left));
+}
+
+#[test]
+fn breathing_shuts_the_door_until_it_stops() {
+    let mut night = night(1);
+    let mut bot = CloseOnBreathing;
+    put(&mut night, "Chica", Locations::SecurityOfficeStaticR);
+
+    let seen = Observation::new(&night, Vec::new(), None);
+    assert!(seen.breathing(Side::Right));
+    assert_eq!(bot.choose(&seen), Command::Close(Side::Right));
+
+    assert!(night.apply(Command::Close(Side::Right)));
+    put(&mut night, "Chica", Locations::HallwayR);
+    let seen = Observation::new(&night, Vec::new(), None);
+    assert_eq!(bot.choose(&seen), Command::Open(Side::Right));
+}
+
+#[test]
+fn reacting_beats_sitting_on_the_same_luck() {
+    let layout = Layout::default();
+    let roster = roster();
+    let survived =
+        |bot: Bot| simulate(&layout, &roster, &[5, 3, 3, 2], AiMode::Hunt, bot, 300, 9).survived;
+
+    let sit = survived(Bot::Sit);
+    assert!(survived(Bot::Breathing) > sit);
+    assert!(survived(Bot::Budget) > sit);
+}